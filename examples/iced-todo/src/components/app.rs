@@ -5,7 +5,7 @@ use hooks_rs::{Context, SetStateAction, create_context, provide_context, use_sta
 use crate::{
     Message,
     components::{Controls, Filter, Header, NewTaskInput, Task, TaskList},
-    react::VNode,
+    react::{VNode, column},
 };
 
 pub static TASKS_CTX: LazyLock<Context<(Vec<Task>, SetStateAction<Vec<Task>>)>> =
@@ -25,7 +25,7 @@ pub fn App(_: ()) -> VNode<Message> {
     provide_context(*INPUT_CTX, (input, set_input));
     provide_context(*FILTER_CTX, (filter, set_filter));
 
-    VNode::column(
+    column(
         vec![
             VNode::component("header", Header, ()),
             VNode::component("new_task_input", NewTaskInput, ()),
@@ -8,7 +8,7 @@ use iced::{
 use crate::{
     Message,
     components::{FILTER_CTX, TASKS_CTX, tasks::Task},
-    react::VNode,
+    react::{VNode, element, row},
 };
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -35,16 +35,16 @@ pub fn Controls(_: ()) -> VNode<Message> {
 
     let tasks_left = tasks.iter().filter(|t| !t.completed).count();
 
-    VNode::row(
+    row(
         vec![
-            VNode::element(
+            element(
                 text!(
                     "{tasks_left} {} left",
                     if tasks_left == 1 { "task" } else { "tasks" }
                 )
                 .width(Fill),
             ),
-            VNode::row(
+            row(
                 vec![
                     VNode::component("filter_button_all", filter_button, ("All", Filter::All)),
                     VNode::component(
@@ -78,12 +78,12 @@ fn filter_button(props: (&'static str, Filter)) -> VNode<Message> {
         iced::widget::button::text
     };
 
-    VNode::element(
+    element(
         button(text(label))
             .style(style)
             .padding(8)
             .on_press_with(move || {
-                set_current_filter(|_| filter);
+                set_current_filter.set(filter);
                 Message::Refresh
             }),
     )
@@ -1,8 +1,11 @@
 use iced::{Alignment::Center, Length::Fill, widget::text};
 
-use crate::{Message, react::VNode};
+use crate::{
+    Message,
+    react::{VNode, element},
+};
 
 #[allow(non_snake_case)]
 pub fn Header(_: ()) -> VNode<Message> {
-    VNode::element(text("todos").size(100).width(Fill).align_x(Center))
+    element(text("todos").size(100).width(Fill).align_x(Center))
 }
@@ -4,7 +4,7 @@ use iced::widget::text_input;
 use crate::{
     Message,
     components::{INPUT_CTX, TASKS_CTX, tasks::Task},
-    react::VNode,
+    react::{VNode, element},
 };
 
 #[allow(non_snake_case)]
@@ -12,20 +12,21 @@ pub fn NewTaskInput(_: ()) -> VNode<Message> {
     let (input, set_input) = use_context(*INPUT_CTX);
     let (_, set_tasks) = use_context(*TASKS_CTX);
 
-    VNode::element(
+    element(
         text_input("What needs to be done?", &input)
             .on_input(move |v| {
-                set_input(|_| v.clone());
+                set_input.set(v);
                 Message::Refresh
             })
             .on_submit_with(move || {
                 if !input.is_empty() {
-                    set_tasks(|prev| {
+                    let input = input.clone();
+                    set_tasks.update(move |prev| {
                         let mut next = prev.clone();
                         next.push(Task::new(input.clone()));
                         next
                     });
-                    set_input(|_| String::new());
+                    set_input.set(String::new());
                 }
                 Message::Refresh
             })
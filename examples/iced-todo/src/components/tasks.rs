@@ -6,7 +6,7 @@ use iced::widget::{button, checkbox, row, text_input};
 use crate::{
     Message,
     components::{FILTER_CTX, TASKS_CTX},
-    react::VNode,
+    react::{VNode, element, keyed_column},
 };
 
 pub static TASK_ID: AtomicU32 = AtomicU32::new(1);
@@ -40,7 +40,7 @@ pub fn TaskList(_: ()) -> VNode<Message> {
         items.push((task.id, VNode::component(key, TaskItem, task)));
     }
 
-    VNode::keyed_column(items, 10)
+    keyed_column(items, 10)
 }
 
 #[allow(non_snake_case)]
@@ -53,14 +53,15 @@ fn TaskItem(task: Task) -> VNode<Message> {
     let (text, set_text) = use_state(move || description);
 
     if editing {
-        VNode::element(row![
+        element(row![
             text_input("Edit task", &text)
                 .on_input(move |v| {
-                    set_text(|_| v.clone());
+                    set_text.set(v);
                     Message::Refresh
                 })
                 .on_submit_with(move || {
-                    set_tasks(|prev| {
+                    let text = text.clone();
+                    set_tasks.update(move |prev| {
                         prev.iter()
                             .map(|t| {
                                 if t.id == task.id {
@@ -74,21 +75,22 @@ fn TaskItem(task: Task) -> VNode<Message> {
                             })
                             .collect()
                     });
-                    set_editing(|_| false);
+                    set_editing.set(false);
                     Message::Refresh
                 }),
             button("Delete").on_press_with(move || {
-                set_tasks(|prev| prev.iter().filter(|t| t.id != task.id).cloned().collect());
+                set_tasks
+                    .update(move |prev| prev.iter().filter(|t| t.id != task.id).cloned().collect());
                 Message::Refresh
             })
         ])
     } else {
         let task_description = task.description.clone();
-        VNode::element(row![
+        element(row![
             checkbox(task.completed)
                 .label(task_description)
                 .on_toggle(move |v| {
-                    set_tasks(|prev| {
+                    set_tasks.update(move |prev| {
                         prev.iter()
                             .map(|t| {
                                 if t.id == task.id {
@@ -105,7 +107,7 @@ fn TaskItem(task: Task) -> VNode<Message> {
                     Message::Refresh
                 }),
             button("Edit").on_press_with(move || {
-                set_editing(|_| true);
+                set_editing.set(true);
                 Message::Refresh
             })
         ])
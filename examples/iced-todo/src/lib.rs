@@ -5,8 +5,12 @@ pub mod react;
 
 use std::cell::RefCell;
 
-use crate::{components::App, react::create_root};
-use iced::Element;
+use crate::{
+    components::App,
+    react::{IcedRenderer, Widget},
+};
+use hooks_rs::{Root, create_root};
+use iced::{Element, widget::stack};
 
 #[derive(Clone, Debug)]
 pub enum Message {
@@ -14,7 +18,7 @@ pub enum Message {
 }
 
 pub struct Todos {
-    root: RefCell<react::Root<(), Message>>,
+    root: RefCell<Root<(), Widget<Message>>>,
 }
 
 impl Todos {
@@ -25,6 +29,13 @@ impl Todos {
     }
     pub fn update(&mut self, _: Message) {}
     pub fn view<'a>(&'a self) -> Element<'a, Message> {
-        self.root.borrow_mut().view(())
+        let rendered = self
+            .root
+            .borrow_mut()
+            .render_with_portals(&mut IcedRenderer::default(), ());
+
+        // Portals, e.g. modals, are drawn over the app.
+        let layers = std::iter::once(rendered.output).chain(rendered.portals.into_values());
+        stack(layers).into()
     }
 }
@@ -1,5 +1,52 @@
-mod node;
-pub use node::*;
+mod renderer;
+pub use renderer::*;
 
-mod reconciler;
-pub use reconciler::*;
+use iced::{Alignment, Element};
+
+/// The UI description returned by components, see [`hooks_rs::VNode`].
+pub type VNode<Message> = hooks_rs::VNode<Widget<Message>>;
+
+/// The host elements of the iced backend.
+pub enum Widget<Message: 'static> {
+    Element(Element<'static, Message>),
+    Column {
+        spacing: u16,
+        padding: u16,
+    },
+    Row {
+        spacing: u16,
+        align_y: Alignment,
+    },
+    /// A column whose children are matched by key, from [`keyed_column`].
+    KeyedColumn {
+        spacing: u16,
+    },
+}
+
+pub fn element<Message: 'static>(el: impl Into<Element<'static, Message>>) -> VNode<Message> {
+    VNode::element(Widget::Element(el.into()))
+}
+
+pub fn column<Message: 'static>(
+    children: Vec<VNode<Message>>,
+    spacing: u16,
+    padding: u16,
+) -> VNode<Message> {
+    VNode::with_children(Widget::Column { spacing, padding }, children)
+}
+
+pub fn row<Message: 'static>(
+    children: Vec<VNode<Message>>,
+    spacing: u16,
+    align_y: Alignment,
+) -> VNode<Message> {
+    VNode::with_children(Widget::Row { spacing, align_y }, children)
+}
+
+pub fn keyed_column<Message: 'static>(
+    items: Vec<(u32, VNode<Message>)>,
+    spacing: u16,
+) -> VNode<Message> {
+    let items = items.into_iter().map(|(key, v)| (key.to_string(), v));
+    VNode::with_children(Widget::KeyedColumn { spacing }, vec![VNode::keyed(items)])
+}
@@ -0,0 +1,53 @@
+use std::marker::PhantomData;
+
+use hooks_rs::{RenderedChild, Renderer};
+use iced::{Element, widget};
+
+use crate::react::Widget;
+
+/// Renders [`Widget`]s into iced elements.
+pub struct IcedRenderer<Message>(PhantomData<Message>);
+
+impl<Message> Default for IcedRenderer<Message> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<Message: 'static> Renderer for IcedRenderer<Message> {
+    type Element = Widget<Message>;
+    type Output = Element<'static, Message>;
+
+    fn element(
+        &mut self,
+        element: Widget<Message>,
+        children: Vec<RenderedChild<Self::Output>>,
+    ) -> Self::Output {
+        match element {
+            Widget::Element(el) => el,
+            Widget::Column { spacing, padding } => widget::column(outputs(children))
+                .spacing(spacing as f32)
+                .padding(padding as f32)
+                .into(),
+            Widget::Row { spacing, align_y } => widget::row(outputs(children))
+                .spacing(spacing as f32)
+                .align_y(align_y)
+                .into(),
+            Widget::KeyedColumn { spacing } => {
+                let items = children.into_iter().map(|c| {
+                    let key = c.key.and_then(|k| k.parse::<u32>().ok());
+                    (key.expect("keyed_column keys are u32"), c.output)
+                });
+                widget::keyed_column(items).spacing(spacing as f32).into()
+            }
+        }
+    }
+
+    fn fragment(&mut self, children: Vec<RenderedChild<Self::Output>>) -> Self::Output {
+        widget::column(outputs(children)).into()
+    }
+}
+
+fn outputs<O>(children: Vec<RenderedChild<O>>) -> impl Iterator<Item = O> {
+    children.into_iter().map(|c| c.output)
+}
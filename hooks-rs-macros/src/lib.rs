@@ -0,0 +1,310 @@
+use proc_macro::TokenStream;
+use quote::{format_ident, quote};
+use syn::{
+    BinOp, Block, Expr, ExprBinary, ExprCall, ExprClosure, ExprForLoop, ExprIf, ExprLoop,
+    ExprMatch, ExprReturn, ExprTry, ExprWhile, FnArg, Ident, Item, ItemFn, Local, Meta, Pat,
+    PatIdent, ReturnType, Token,
+    parse::Parser,
+    parse_macro_input,
+    punctuated::Punctuated,
+    visit::{self, Visit},
+};
+
+/// Turns a function component into a `hooks_rs::Component` with a props struct and a typed handle.
+///
+/// ```rust,ignore
+/// #[component]
+/// fn Counter(step: i32, label: String) -> String { .. }
+/// ```
+///
+/// expands to:
+///
+/// - `struct CounterProps { step: i32, label: String }` (or `()` without parameters),
+/// - a unit struct `Counter` implementing `Component`, documented by the
+///   function's doc comments,
+/// - `Counter::mount(parent, id)`, returning a `FiberHandle<CounterProps, String>`.
+///
+/// The attribute's arguments are forwarded onto the props struct, e.g.
+/// `#[component(derive(Clone, Debug))]` for props a `TestHarness` can render.
+///
+/// Hooks (`use_*` and `provide_context`) called inside conditionals, loops or
+/// closures, or after an early `return` or `?`, are rejected at compile time,
+/// since hook identity depends on call order. Macro invocations are opaque to
+/// this check: hooks, returns and `?` inside them aren't seen.
+#[proc_macro_attribute]
+pub fn component(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let props_attrs = match Punctuated::<Meta, Token![,]>::parse_terminated.parse(attr) {
+        Ok(attrs) => attrs,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    let item = parse_macro_input!(item as ItemFn);
+    match expand_component(item, props_attrs) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn expand_component(
+    item: ItemFn,
+    props_attrs: Punctuated<Meta, Token![,]>,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let ItemFn {
+        attrs,
+        vis,
+        sig,
+        block,
+    } = item;
+
+    if !sig.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &sig.generics,
+            "components can't be generic",
+        ));
+    }
+    if let Some(asyncness) = &sig.asyncness {
+        return Err(syn::Error::new_spanned(
+            asyncness,
+            "components can't be async",
+        ));
+    }
+
+    check_hook_calls(&block)?;
+
+    let name = &sig.ident;
+    let output = match &sig.output {
+        ReturnType::Default => quote! { () },
+        ReturnType::Type(_, ty) => quote! { #ty },
+    };
+
+    let mut fields = Vec::new();
+    for input in &sig.inputs {
+        let FnArg::Typed(arg) = input else {
+            return Err(syn::Error::new_spanned(
+                input,
+                "components can't take `self`",
+            ));
+        };
+        let Pat::Ident(pat @ PatIdent { subpat: None, .. }) = &*arg.pat else {
+            return Err(syn::Error::new_spanned(
+                &arg.pat,
+                "component parameters must be plain identifiers",
+            ));
+        };
+        fields.push((pat, &arg.ty));
+    }
+
+    let props_name = format_ident!("{}Props", to_upper_camel_case(&name.to_string()));
+    let (props_struct, props_ty, destructure) = if fields.is_empty() {
+        if !props_attrs.is_empty() {
+            return Err(syn::Error::new_spanned(
+                &props_attrs,
+                "components without parameters take `()` as props, there is no struct to forward attributes to",
+            ));
+        }
+        (quote! {}, quote! { () }, quote! { let () = props; })
+    } else {
+        let props_attrs = props_attrs.iter();
+        let idents: Vec<&Ident> = fields.iter().map(|(pat, _)| &pat.ident).collect();
+        let types = fields.iter().map(|(_, ty)| ty);
+        // Keep `mut` and `ref` from the parameters when binding the fields.
+        let bindings = fields.iter().map(|(pat, _)| {
+            let PatIdent {
+                by_ref,
+                mutability,
+                ident,
+                ..
+            } = pat;
+            quote! { #by_ref #mutability #ident }
+        });
+        (
+            quote! {
+                #(#[#props_attrs])*
+                #vis struct #props_name {
+                    #(pub #idents: #types,)*
+                }
+            },
+            quote! { #props_name },
+            quote! { let #props_name { #(#bindings,)* } = props; },
+        )
+    };
+
+    // Doc comments describe the component, other attributes apply to its body.
+    let (docs, fn_attrs): (Vec<_>, Vec<_>) =
+        attrs.iter().partition(|attr| attr.path().is_ident("doc"));
+
+    Ok(quote! {
+        #props_struct
+
+        #(#docs)*
+        #[allow(non_camel_case_types)]
+        #vis struct #name;
+
+        impl ::hooks_rs::Component for #name {
+            type Props = #props_ty;
+            type Output = #output;
+
+            #(#fn_attrs)*
+            fn render(props: Self::Props) -> Self::Output {
+                #destructure
+                #block
+            }
+        }
+
+        impl #name {
+            /// Mount this component in the global fiber tree.
+            #vis fn mount(
+                parent: ::std::option::Option<::std::string::String>,
+                id: impl ::std::convert::Into<::std::string::String>,
+            ) -> ::std::result::Result<
+                ::hooks_rs::FiberHandle<#props_ty, #output>,
+                ::hooks_rs::FiberStoreError,
+            > {
+                ::hooks_rs::mount_component::<Self>(parent, id)
+            }
+        }
+    })
+}
+
+fn to_upper_camel_case(name: &str) -> String {
+    name.split('_')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            let first = chars.next().unwrap().to_ascii_uppercase();
+            std::iter::once(first).chain(chars).collect::<String>()
+        })
+        .collect()
+}
+
+// ------------------------------------ Hook call checks ------------------------------------
+
+fn check_hook_calls(block: &Block) -> syn::Result<()> {
+    let mut checker = HookCallChecker::default();
+    checker.visit_block(block);
+
+    let mut errors = checker.errors.into_iter();
+    let Some(mut first) = errors.next() else {
+        return Ok(());
+    };
+    for err in errors {
+        first.combine(err);
+    }
+    Err(first)
+}
+
+/// Walks a component body and reports hooks called where they may not run on every render.
+#[derive(Default)]
+struct HookCallChecker {
+    /// Innermost construct that makes the current code run conditionally or repeatedly.
+    scopes: Vec<&'static str>,
+    /// Set once the component may have returned early, since later code may not run.
+    exit: Option<&'static str>,
+    errors: Vec<syn::Error>,
+}
+
+impl HookCallChecker {
+    fn scoped(&mut self, scope: &'static str, f: impl FnOnce(&mut Self)) {
+        self.scopes.push(scope);
+        f(self);
+        self.scopes.pop();
+    }
+}
+
+fn hook_name(func: &Expr) -> Option<String> {
+    let Expr::Path(path) = func else {
+        return None;
+    };
+    let name = path.path.segments.last()?.ident.to_string();
+    (name.starts_with("use_") || name == "provide_context").then_some(name)
+}
+
+impl<'ast> Visit<'ast> for HookCallChecker {
+    fn visit_expr_call(&mut self, call: &'ast ExprCall) {
+        let position = match (self.scopes.last(), self.exit) {
+            (Some(scope), _) => Some(format!("inside {scope}")),
+            (None, Some(exit)) => Some(format!("after {exit}")),
+            (None, None) => None,
+        };
+        if let (Some(name), Some(position)) = (hook_name(&call.func), position) {
+            let message = format!(
+                "hook `{name}` can't be called {position}, \
+                 hooks must run in the same order on every render"
+            );
+            self.errors.push(syn::Error::new_spanned(call, message));
+        }
+        visit::visit_expr_call(self, call);
+    }
+
+    fn visit_expr_return(&mut self, expr: &'ast ExprReturn) {
+        visit::visit_expr_return(self, expr);
+        self.exit.get_or_insert("an early return");
+    }
+
+    fn visit_expr_try(&mut self, expr: &'ast ExprTry) {
+        visit::visit_expr_try(self, expr);
+        self.exit.get_or_insert("the `?` operator");
+    }
+
+    fn visit_expr_if(&mut self, expr: &'ast ExprIf) {
+        self.visit_expr(&expr.cond);
+        self.scoped("a conditional", |v| {
+            v.visit_block(&expr.then_branch);
+            if let Some((_, else_branch)) = &expr.else_branch {
+                v.visit_expr(else_branch);
+            }
+        });
+    }
+
+    fn visit_expr_match(&mut self, expr: &'ast ExprMatch) {
+        self.visit_expr(&expr.expr);
+        self.scoped("a match arm", |v| {
+            for arm in &expr.arms {
+                v.visit_arm(arm);
+            }
+        });
+    }
+
+    fn visit_expr_binary(&mut self, expr: &'ast ExprBinary) {
+        if matches!(expr.op, BinOp::And(_) | BinOp::Or(_)) {
+            self.visit_expr(&expr.left);
+            self.scoped("a conditional", |v| v.visit_expr(&expr.right));
+        } else {
+            visit::visit_expr_binary(self, expr);
+        }
+    }
+
+    fn visit_expr_while(&mut self, expr: &'ast ExprWhile) {
+        self.scoped("a loop", |v| visit::visit_expr_while(v, expr));
+    }
+
+    fn visit_expr_for_loop(&mut self, expr: &'ast ExprForLoop) {
+        self.visit_expr(&expr.expr);
+        self.scoped("a loop", |v| v.visit_block(&expr.body));
+    }
+
+    fn visit_expr_loop(&mut self, expr: &'ast ExprLoop) {
+        self.scoped("a loop", |v| visit::visit_expr_loop(v, expr));
+    }
+
+    fn visit_expr_closure(&mut self, expr: &'ast ExprClosure) {
+        // Returning from a closure doesn't return from the component.
+        let exit = self.exit;
+        self.scoped("a closure", |v| visit::visit_expr_closure(v, expr));
+        self.exit = exit;
+    }
+
+    fn visit_local(&mut self, local: &'ast Local) {
+        let Some(init) = &local.init else {
+            return visit::visit_local(self, local);
+        };
+        self.visit_pat(&local.pat);
+        self.visit_expr(&init.expr);
+        if let Some((_, diverge)) = &init.diverge {
+            self.scoped("a conditional", |v| v.visit_expr(diverge));
+        }
+    }
+
+    // Nested items are separate functions with their own hook rules.
+    fn visit_item(&mut self, _: &'ast Item) {}
+}
@@ -6,6 +6,7 @@ pub enum FiberStoreError {
     FiberDoesntExist(String),
     FiberTypeMismatch(String),
     ParentDoesNotExist(String),
+    MoveIntoDescendant(String),
 }
 
 impl Display for FiberStoreError {
@@ -23,6 +24,13 @@ impl Display for FiberStoreError {
             FiberStoreError::ParentDoesNotExist(id) => {
                 write!(f, "Parent fiber with id {} doesn't exist", id)
             }
+            FiberStoreError::MoveIntoDescendant(id) => {
+                write!(
+                    f,
+                    "Fiber with id {} can't be moved into its own subtree",
+                    id
+                )
+            }
         }
     }
 }
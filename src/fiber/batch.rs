@@ -0,0 +1,66 @@
+use std::cell::{Cell, RefCell};
+
+use crate::fiber::{CURRENT_FIBER_ID, HooksState};
+
+type Update = Box<dyn FnOnce()>;
+
+thread_local! {
+    static BATCH_DEPTH: Cell<usize> = const { Cell::new(0) };
+    /// Queued updates, with the uid of the fiber state they write to.
+    static PENDING_UPDATES: RefCell<Vec<(u64, Update)>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Run `f` as a single transaction.
+///
+/// State updates made inside `f` are queued and applied in order once the
+/// outermost `batch` returns, so every affected fiber is marked dirty once.
+/// Updates of fibers unmounted in the meantime are dropped. Batches can be
+/// nested.
+pub fn batch<T>(f: impl FnOnce() -> T) -> T {
+    struct DepthGuard;
+
+    impl Drop for DepthGuard {
+        fn drop(&mut self) {
+            BATCH_DEPTH.with(|d| d.set(d.get() - 1));
+        }
+    }
+
+    BATCH_DEPTH.with(|d| d.set(d.get() + 1));
+    let result = {
+        let _guard = DepthGuard;
+        f()
+    };
+
+    flush_updates();
+    result
+}
+
+/// Apply `update` to the fiber state `uid` now, or queue it if a batch or a
+/// render is in progress.
+pub(crate) fn schedule_update(uid: u64, update: Update) {
+    if is_idle() {
+        update();
+    } else {
+        PENDING_UPDATES.with(|q| q.borrow_mut().push((uid, update)));
+    }
+}
+
+/// Apply every queued update, unless a batch or a render is still in progress.
+pub(crate) fn flush_updates() {
+    while is_idle() {
+        let updates = PENDING_UPDATES.with(|q| std::mem::take(&mut *q.borrow_mut()));
+        if updates.is_empty() {
+            break;
+        }
+        for (uid, update) in updates {
+            // The fiber may have been unmounted since the update was queued.
+            if HooksState::is_live(uid) {
+                update();
+            }
+        }
+    }
+}
+
+fn is_idle() -> bool {
+    BATCH_DEPTH.with(|d| d.get()) == 0 && CURRENT_FIBER_ID.with(|id| id.borrow().is_none())
+}
@@ -0,0 +1,164 @@
+use std::marker::PhantomData;
+
+use crate::{
+    FiberStoreError,
+    fiber::{call_fiber, mount_fiber, unmount_fiber},
+};
+
+/// A function component with a dedicated props type.
+///
+/// Usually implemented with the [`component`](crate::component) attribute,
+/// which turns the function's parameters into a props struct:
+///
+/// ```rust
+/// use hooks_rs::{component, use_state};
+///
+/// #[component]
+/// fn Counter(step: i32) -> i32 {
+///     let (count, set_count) = use_state(|| 0);
+///     set_count.update(move |prev| prev + step);
+///     count
+/// }
+///
+/// let counter = Counter::mount(None, "counter").unwrap();
+/// assert_eq!(counter.call(CounterProps { step: 2 }).unwrap(), 0);
+/// assert_eq!(counter.call(CounterProps { step: 2 }).unwrap(), 2);
+/// ```
+///
+/// The attribute's arguments are forwarded onto the props struct:
+///
+/// ```rust
+/// use hooks_rs::{Component, component, testing::TestHarness};
+///
+/// #[component(derive(Clone, Debug))]
+/// fn Label(text: String) -> String {
+///     text
+/// }
+///
+/// let mut harness = TestHarness::new(Label::render);
+/// assert_eq!(harness.render(LabelProps { text: "hi".into() }), "hi");
+/// ```
+///
+/// Hooks called inside conditionals, loops or closures are rejected at compile time:
+///
+/// ```rust,compile_fail
+/// use hooks_rs::{component, use_state};
+///
+/// #[component]
+/// fn Conditional(enabled: bool) -> i32 {
+///     if enabled {
+///         let (count, _) = use_state(|| 0);
+///         return count;
+///     }
+///     0
+/// }
+/// ```
+///
+/// ```rust,compile_fail
+/// use hooks_rs::{component, use_state};
+///
+/// #[component]
+/// fn Repeated(times: usize) -> i32 {
+///     let mut total = 0;
+///     for _ in 0..times {
+///         let (count, _) = use_state(|| 1);
+///         total += count;
+///     }
+///     total
+/// }
+/// ```
+///
+/// ```rust,compile_fail
+/// use hooks_rs::{component, use_state};
+///
+/// #[component]
+/// fn Deferred() -> i32 {
+///     let read = || use_state(|| 1).0;
+///     read()
+/// }
+/// ```
+///
+/// So are hooks called after an early `return` or `?`:
+///
+/// ```rust,compile_fail
+/// use hooks_rs::{component, use_state};
+///
+/// #[component]
+/// fn Early(skip: bool) -> i32 {
+///     if skip {
+///         return 0;
+///     }
+///     let (count, _) = use_state(|| 1);
+///     count
+/// }
+/// ```
+///
+/// ```rust,compile_fail
+/// use hooks_rs::{component, use_state};
+///
+/// #[component]
+/// fn Parsed(input: String) -> Result<i32, std::num::ParseIntError> {
+///     let step: i32 = input.parse()?;
+///     let (count, _) = use_state(|| step);
+///     Ok(count)
+/// }
+/// ```
+///
+/// Hooks inside macro invocations aren't checked, e.g. `vec![use_state(|| 0)]`.
+pub trait Component: 'static {
+    type Props: 'static;
+    type Output: 'static;
+
+    fn render(props: Self::Props) -> Self::Output;
+}
+
+/// A typed handle to a mounted fiber, so props and output types don't have to be spelled out.
+pub struct FiberHandle<P, R> {
+    id: String,
+    _marker: PhantomData<fn(P) -> R>,
+}
+
+impl<P, R> FiberHandle<P, R> {
+    /// Create a handle for an already mounted fiber.
+    pub fn new(id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// The id of the fiber.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Call the fiber with `props`.
+    pub fn call(&self, props: P) -> Result<R, FiberStoreError>
+    where
+        P: 'static,
+        R: 'static,
+    {
+        call_fiber(self.id.clone(), props)
+    }
+
+    /// Unmount the fiber (and all descendants).
+    pub fn unmount(self) {
+        unmount_fiber(self.id)
+    }
+}
+
+impl<P, R> Clone for FiberHandle<P, R> {
+    fn clone(&self) -> Self {
+        Self::new(self.id.clone())
+    }
+}
+
+/// Mount a [`Component`] in the global fiber tree and return a typed handle to it.
+pub fn mount_component<C: Component>(
+    parent: Option<String>,
+    id: impl Into<String>,
+) -> Result<FiberHandle<C::Props, C::Output>, FiberStoreError> {
+    let id = id.into();
+    mount_fiber(parent, id.clone(), C::render)?;
+    Ok(FiberHandle::new(id))
+}
@@ -7,26 +7,185 @@ pub(crate) use node::*;
 mod tree;
 pub(crate) use tree::*;
 
+mod batch;
+pub use batch::batch;
+pub(crate) use batch::*;
+
+mod handle;
+pub use handle::*;
+
+mod observer;
+pub use observer::*;
+
+mod reconcile;
+mod remote;
+pub(crate) use remote::RemoteSender;
+pub use remote::process_remote_updates;
+mod reparent;
+pub use reconcile::ChildChanges;
+
 use crate::FiberStoreError;
 
+/// Whether strict mode is enabled (`strict` cargo feature).
+///
+/// In strict mode effects are mounted, cleaned up and mounted again on the
+/// first render of a fiber, to surface effects that don't clean up after
+/// themselves. Fibers still render once per call: rendering twice would need
+/// to clone the props, which would make the feature add a bound to
+/// [`call_fiber`].
+pub(crate) fn is_strict_mode() -> bool {
+    cfg!(feature = "strict")
+}
+
 /// Mount a fiber in the global fiber tree.
+///
+/// `fun` renders the fiber. It can be a function or a closure, which keeps
+/// whatever it captured across renders.
 pub fn mount_fiber<P, R>(
     parent: Option<String>,
     id: impl Into<String>,
-    fun: fn(P) -> R,
+    fun: impl FnMut(P) -> R + 'static,
+) -> Result<(), FiberStoreError>
+where
+    P: 'static,
+    R: 'static,
+{
+    insert_fiber(parent, id.into(), None, fun)
+}
+
+/// Mount a fiber at `index` among the children of `parent`.
+///
+/// An index past the last child appends the fiber.
+pub fn mount_fiber_at<P, R>(
+    parent: impl Into<String>,
+    id: impl Into<String>,
+    index: usize,
+    fun: impl FnMut(P) -> R + 'static,
+) -> Result<(), FiberStoreError>
+where
+    P: 'static,
+    R: 'static,
+{
+    insert_fiber(Some(parent.into()), id.into(), Some(index), fun)
+}
+
+/// Shared by the mount functions: inserts the fiber, then notifies the observers.
+fn insert_fiber<P, R>(
+    parent: Option<String>,
+    id: String,
+    index: Option<usize>,
+    fun: impl FnMut(P) -> R + 'static,
+) -> Result<(), FiberStoreError>
+where
+    P: 'static,
+    R: 'static,
+{
+    FIBER_TREE.with(|t| {
+        let mut tree = t.borrow_mut();
+        match index {
+            Some(index) => tree.insert_fiber(parent.clone(), id.clone(), Some(index), fun),
+            None => tree.mount_fiber(parent.clone(), id.clone(), fun),
+        }
+    })?;
+
+    #[cfg(feature = "tracing")]
+    tracing::debug!(fiber = %id, parent = ?parent, "mount");
+    notify(|observer| observer.on_mount(&id));
+    Ok(())
+}
+
+/// Swap the function of a mounted fiber, e.g. after its code was reloaded.
+///
+/// The props and output types may change. The hooks state is kept if the new
+/// function calls the same hooks in the same order. Otherwise the state is
+/// reset after the next render and the fiber is marked dirty so it renders
+/// again from scratch. Setters and slot refs of the old hooks then behave as
+/// after an unmount.
+///
+/// Without the `tracing` feature, which logs a warning, a reset is silent:
+/// call [`was_fiber_reset`] after the render to find out.
+pub fn replace_fiber_fn<P, R>(
+    id: impl Into<String>,
+    fun: impl FnMut(P) -> R + 'static,
 ) -> Result<(), FiberStoreError>
 where
     P: 'static,
     R: 'static,
 {
     let id = id.into();
-    FIBER_TREE.with(|t| t.borrow_mut().mount_fiber(parent, id, fun))
+    FIBER_TREE.with(|t| t.borrow_mut().replace_fiber_fn(id, fun))
 }
 
 /// Unmount a fiber (and all descendants) from the global fiber tree.
+///
+/// Fibers are torn down depth-first, children before their parent and
+/// siblings in order. Within a fiber, hooks are finalized in reverse
+/// declaration order: effect cleanups and `on_unmount` callbacks run, then the
+/// hook's state is dropped, before moving on to the previous hook.
 pub fn unmount_fiber(id: impl Into<String>) {
     let id = id.into();
-    FIBER_TREE.with(|t| t.borrow_mut().unmount_fiber(id))
+    let detached = FIBER_TREE.with(|t| t.borrow_mut().detach_fiber(id));
+
+    // Teardown runs outside of the tree borrow, so cleanups may use the tree.
+    for node in detached {
+        node.unmount();
+    }
+}
+
+/// Move a fiber and its descendants under `new_parent`, keeping their hooks state.
+///
+/// The fiber is inserted at `index` among the children of `new_parent`, or
+/// last if the index is past the end. It becomes a root if `new_parent` is `None`.
+///
+/// Ids built from the parent's id, like `parent/key`, are rewritten for the
+/// new parent, in the whole subtree. The moved fibers are marked dirty, so
+/// they read their contexts from their new ancestors on the next render.
+///
+/// Returns the new id of the fiber.
+pub fn move_fiber(
+    id: impl Into<String>,
+    new_parent: Option<String>,
+    index: usize,
+) -> Result<String, FiberStoreError> {
+    let id = id.into();
+
+    #[cfg(feature = "tracing")]
+    tracing::debug!(fiber = %id, parent = ?new_parent, index, "move");
+
+    FIBER_TREE.with(|t| t.borrow_mut().move_fiber(&id, new_parent, Some(index)))
+}
+
+/// Order the children of `parent` like `next`, unmounting those that aren't in it.
+///
+/// Ids of `next` that aren't children yet are reported as inserted, mount them
+/// afterwards with [`mount_fiber_at`] to keep the order.
+///
+/// # Panics
+///
+/// Panics if `next` contains the same id twice.
+pub fn reconcile_children(
+    parent: impl Into<String>,
+    next: &[String],
+) -> Result<ChildChanges, FiberStoreError> {
+    let parent = parent.into();
+    let (changes, detached) =
+        FIBER_TREE.with(|t| t.borrow_mut().reconcile_children(&parent, next))?;
+
+    #[cfg(feature = "tracing")]
+    if !changes.is_empty() {
+        tracing::debug!(
+            fiber = %parent,
+            inserted = ?changes.inserted,
+            removed = ?changes.removed,
+            moved = ?changes.moved,
+            "reconcile"
+        );
+    }
+
+    for node in detached {
+        node.unmount();
+    }
+    Ok(changes)
 }
 
 /// Call a fiber from the global fiber tree.
@@ -46,7 +205,15 @@ where
         Ok(node.fiber.clone())
     })?;
 
-    CURRENT_FIBER_ID.with(|cell| *cell.borrow_mut() = Some(id.clone()));
+    #[cfg(feature = "tracing")]
+    let _span = tracing::debug_span!(
+        "call_fiber",
+        fiber = %id,
+        props = std::any::type_name::<P>()
+    )
+    .entered();
+
+    let prev_id = CURRENT_FIBER_ID.with(|cell| cell.replace(Some(id.clone())));
 
     let fiber_ptr = {
         let mut fiber_any = fiber_rc.borrow_mut();
@@ -57,9 +224,24 @@ where
         fiber as *mut Fiber<P, R>
     };
 
+    let render = crate::profiler::begin_render(unsafe { &(*fiber_ptr).state }, prev_id.as_ref());
+    notify(|observer| observer.on_render_start(&id));
+
     let res = unsafe { (&mut *fiber_ptr).call(props) };
 
-    CURRENT_FIBER_ID.with(|cell| *cell.borrow_mut() = None);
+    if let Some(render) = render {
+        crate::profiler::end_render(render, unsafe { &(*fiber_ptr).state });
+    }
+
+    notify(|observer| observer.on_render_end(&id));
+
+    CURRENT_FIBER_ID.with(|cell| *cell.borrow_mut() = prev_id);
+
+    unsafe { (*fiber_ptr).state.commit() };
+    notify(|observer| observer.on_commit(&id));
+
+    // Updates made during the render are applied once it has finished.
+    flush_updates();
 
     Ok(res)
 }
@@ -72,11 +254,50 @@ pub fn get_children_ids(id: impl Into<String>) -> Result<Vec<String>, FiberStore
         let node = tree
             .0
             .get(&id)
-            .ok_or_else(|| FiberStoreError::FiberDoesntExist(id))?;
+            .ok_or(FiberStoreError::FiberDoesntExist(id))?;
         Ok(node.children.clone())
     })
 }
 
+/// Returns whether a fiber has state updates that haven't been rendered yet.
+pub fn is_fiber_dirty(id: impl Into<String>) -> Result<bool, FiberStoreError> {
+    let id = id.into();
+    FIBER_TREE.with(|t| {
+        let tree = t.borrow();
+        let node = tree
+            .0
+            .get(&id)
+            .ok_or(FiberStoreError::FiberDoesntExist(id))?;
+        Ok(node.is_dirty())
+    })
+}
+
+/// Returns whether the fiber's last render reset its state, because its
+/// function was replaced by one calling different hooks (see [`replace_fiber_fn`]).
+pub fn was_fiber_reset(id: impl Into<String>) -> Result<bool, FiberStoreError> {
+    let id = id.into();
+    FIBER_TREE.with(|t| {
+        let tree = t.borrow();
+        let node = tree
+            .0
+            .get(&id)
+            .ok_or(FiberStoreError::FiberDoesntExist(id))?;
+        Ok(node.was_reset())
+    })
+}
+
+/// Gets the ids of every fiber with state updates that haven't been rendered yet.
+pub fn get_dirty_ids() -> Vec<String> {
+    FIBER_TREE.with(|t| {
+        let tree = t.borrow();
+        tree.0
+            .iter()
+            .filter(|(_, node)| node.is_dirty())
+            .map(|(id, _)| id.clone())
+            .collect()
+    })
+}
+
 /// Gets the parent id of a fiber node.
 pub fn get_parent_id(id: impl Into<String>) -> Result<Option<String>, FiberStoreError> {
     let id = id.into();
@@ -85,7 +306,7 @@ pub fn get_parent_id(id: impl Into<String>) -> Result<Option<String>, FiberStore
         let node = tree
             .0
             .get(&id)
-            .ok_or_else(|| FiberStoreError::FiberDoesntExist(id))?;
+            .ok_or(FiberStoreError::FiberDoesntExist(id))?;
         Ok(node.parent.clone())
     })
 }
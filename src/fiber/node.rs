@@ -1,23 +1,54 @@
-use std::any::Any;
+use std::any::{Any, TypeId};
 
 use crate::fiber::HooksState;
 
+type Render<P, R> = Box<dyn FnMut(P) -> R>;
+
 pub(crate) struct Fiber<P, R> {
-    pub(crate) fun: fn(P) -> R,
-    pub(crate) state: HooksState,
+    pub(crate) fun: Render<P, R>,
+    /// Type of the function, which tells apart the components of a `VNode` tree.
+    pub(crate) fun_type: TypeId,
+    /// Boxed so setters keep pointing at it when the fiber's function is replaced.
+    pub(crate) state: Box<HooksState>,
 }
 
 impl<P, R> Fiber<P, R> {
-    pub(crate) fn new(fun: fn(P) -> R) -> Self {
-        let state = HooksState::new();
-        Self { fun, state }
+    pub(crate) fn new<F: FnMut(P) -> R + 'static>(id: String, fun: F) -> Self {
+        let state = Box::new(HooksState::new(id));
+        Self {
+            fun: Box::new(fun),
+            fun_type: TypeId::of::<F>(),
+            state,
+        }
     }
     pub(crate) fn call(&mut self, args: P) -> R {
-        self.state.hook_index = 0;
+        self.state.begin_render();
 
         // Execute the Fiber and get the result
         let result = (self.fun)(args);
 
+        #[cfg(feature = "serde")]
+        if self.state.hydration_report.is_some() {
+            crate::snapshot::finish_hydration(&mut self.state);
+        }
+
+        if self.state.reload.is_some() && !self.state.finish_reload() {
+            #[cfg(feature = "tracing")]
+            tracing::warn!(
+                fiber = %self.state.fiber_id,
+                "replaced by a function with a different hook layout, state reset"
+            );
+            return result;
+        }
+
+        #[cfg(any(debug_assertions, feature = "verify-hooks"))]
+        if let Err(diff) = self.state.verify_hook_order() {
+            panic!(
+                "Hook order changed in fiber `{}`: {diff}",
+                self.state.fiber_id
+            );
+        }
+
         result
     }
 }
@@ -26,6 +57,7 @@ pub(crate) trait ErasedFiber: Any {
     fn as_any_mut(&mut self) -> &mut dyn Any;
     fn state_ptr_mut(&mut self) -> *mut HooksState;
     fn state_ptr(&self) -> *const HooksState;
+    fn into_state(self: Box<Self>) -> Box<HooksState>;
 }
 
 impl<P, R> ErasedFiber for Fiber<P, R>
@@ -38,10 +70,14 @@ where
     }
 
     fn state_ptr_mut(&mut self) -> *mut HooksState {
-        &mut self.state as *mut HooksState
+        &mut *self.state as *mut HooksState
     }
 
     fn state_ptr(&self) -> *const HooksState {
-        &self.state as *const HooksState
+        &*self.state as *const HooksState
+    }
+
+    fn into_state(self: Box<Self>) -> Box<HooksState> {
+        self.state
     }
 }
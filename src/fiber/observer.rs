@@ -0,0 +1,101 @@
+use std::{
+    rc::Rc,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use crate::fiber::{FIBER_TREE, FiberTree};
+
+static NEXT_OBSERVER_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Receives the lifecycle events of the fibers of a tree, see [`add_observer`].
+///
+/// Every method does nothing by default. Observers are shared, so the ones
+/// keeping track of something need interior mutability.
+#[allow(unused_variables)]
+pub trait FiberObserver: 'static {
+    /// A fiber was mounted.
+    fn on_mount(&self, id: &str) {}
+    /// A fiber is about to render.
+    fn on_render_start(&self, id: &str) {}
+    /// A fiber finished rendering, its hooks aren't committed yet.
+    fn on_render_end(&self, id: &str) {}
+    /// The hooks of a fiber were committed after a render.
+    fn on_commit(&self, id: &str) {}
+    /// A setter changed the state of the hook at `hook_index`.
+    fn on_state_change(&self, id: &str, hook_index: usize) {}
+    /// A fiber was unmounted, after its hooks were torn down.
+    fn on_unmount(&self, id: &str) {}
+}
+
+/// Identifies an observer added with [`add_observer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ObserverId(u64);
+
+impl FiberTree {
+    /// Add an observer notified of the lifecycle events of the tree's fibers.
+    pub fn add_observer(&mut self, observer: impl FiberObserver) -> ObserverId {
+        let id = ObserverId(NEXT_OBSERVER_ID.fetch_add(1, Ordering::Relaxed));
+        self.1.push((id, Rc::new(observer)));
+        id
+    }
+
+    /// Remove an observer. Returns `false` if it wasn't added to this tree.
+    pub fn remove_observer(&mut self, id: ObserverId) -> bool {
+        let len = self.1.len();
+        self.1.retain(|(observer, _)| *observer != id);
+        self.1.len() != len
+    }
+}
+
+/// Add an observer to the global fiber tree.
+///
+/// ```rust
+/// use std::{cell::RefCell, rc::Rc};
+///
+/// use hooks_rs::{FiberObserver, add_observer, call_fiber, mount_fiber, unmount_fiber};
+///
+/// #[derive(Clone, Default)]
+/// struct Log(Rc<RefCell<Vec<String>>>);
+///
+/// impl FiberObserver for Log {
+///     fn on_mount(&self, id: &str) {
+///         self.0.borrow_mut().push(format!("mount {id}"));
+///     }
+///
+///     fn on_unmount(&self, id: &str) {
+///         self.0.borrow_mut().push(format!("unmount {id}"));
+///     }
+/// }
+///
+/// let log = Log::default();
+/// add_observer(log.clone());
+///
+/// mount_fiber(None, "root", |_: ()| {}).unwrap();
+/// unmount_fiber("root");
+/// assert_eq!(*log.0.borrow(), ["mount root", "unmount root"]);
+/// ```
+pub fn add_observer(observer: impl FiberObserver) -> ObserverId {
+    FIBER_TREE.with(|t| t.borrow_mut().add_observer(observer))
+}
+
+/// Remove an observer from the global fiber tree.
+pub fn remove_observer(id: ObserverId) -> bool {
+    FIBER_TREE.with(|t| t.borrow_mut().remove_observer(id))
+}
+
+/// Runs `f` for every observer of the global fiber tree.
+///
+/// Observers are called once the tree is no longer borrowed, so they may use it.
+pub(crate) fn notify(f: impl Fn(&dyn FiberObserver)) {
+    let observers: Vec<Rc<dyn FiberObserver>> = FIBER_TREE.with(|t| {
+        let tree = t.borrow();
+        tree.1
+            .iter()
+            .map(|(_, observer)| observer.clone())
+            .collect()
+    });
+
+    for observer in observers {
+        f(&*observer);
+    }
+}
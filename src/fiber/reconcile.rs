@@ -0,0 +1,121 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    FiberStoreError,
+    fiber::{FiberNode, FiberTree},
+};
+
+/// What [`reconcile_children`](crate::reconcile_children) changed in the
+/// children of a fiber.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChildChanges {
+    /// Ids that weren't children yet, with their index in the new order.
+    /// They are expected to be mounted by the caller.
+    pub inserted: Vec<(String, usize)>,
+    /// Children that were unmounted, in their previous order.
+    pub removed: Vec<String>,
+    /// Children that have to move to reach the new order, with their new index.
+    ///
+    /// Children in the longest run that kept its relative order stay in place,
+    /// so this is the minimal set of moves.
+    pub moved: Vec<(String, usize)>,
+}
+
+impl ChildChanges {
+    pub fn is_empty(&self) -> bool {
+        self.inserted.is_empty() && self.removed.is_empty() && self.moved.is_empty()
+    }
+}
+
+impl FiberTree {
+    /// Orders the children of `parent` like `next`, detaching the children
+    /// that aren't in it.
+    ///
+    /// Returns the changes and the detached nodes, which still have to be
+    /// unmounted once the tree is no longer borrowed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `next` contains the same id twice.
+    pub(crate) fn reconcile_children(
+        &mut self,
+        parent: &str,
+        next: &[String],
+    ) -> Result<(ChildChanges, Vec<FiberNode>), FiberStoreError> {
+        let mut seen = HashSet::with_capacity(next.len());
+        for id in next {
+            if !seen.insert(id) {
+                panic!("Duplicate key `{id}` among the children of `{parent}`.");
+            }
+        }
+
+        let prev = self
+            .0
+            .get(parent)
+            .ok_or_else(|| FiberStoreError::FiberDoesntExist(parent.to_string()))?
+            .children
+            .clone();
+        let prev_index: HashMap<&String, usize> =
+            prev.iter().enumerate().map(|(i, id)| (id, i)).collect();
+
+        let mut changes = ChildChanges::default();
+        let mut detached = Vec::new();
+        for id in prev.iter().filter(|id| !seen.contains(id)) {
+            changes.removed.push(id.clone());
+            detached.extend(self.detach_fiber(id.clone()));
+        }
+
+        // Previous positions of the kept children, in their new order.
+        let mut kept = Vec::new();
+        for (index, id) in next.iter().enumerate() {
+            match prev_index.get(id) {
+                Some(&prev) => kept.push((index, prev)),
+                None => changes.inserted.push((id.clone(), index)),
+            }
+        }
+
+        let stays =
+            longest_increasing_subsequence(&kept.iter().map(|&(_, p)| p).collect::<Vec<_>>());
+        for (i, &(index, _)) in kept.iter().enumerate() {
+            if !stays.contains(&i) {
+                changes.moved.push((next[index].clone(), index));
+            }
+        }
+
+        let node = self.0.get_mut(parent).expect("checked above");
+        node.children = next
+            .iter()
+            .filter(|id| prev_index.contains_key(id))
+            .cloned()
+            .collect();
+
+        Ok((changes, detached))
+    }
+}
+
+/// Indices of a longest strictly increasing subsequence of `values`.
+fn longest_increasing_subsequence(values: &[usize]) -> HashSet<usize> {
+    // `tails[k]` is the index of the smallest value ending a run of length `k + 1`.
+    let mut tails: Vec<usize> = Vec::new();
+    let mut prev: Vec<Option<usize>> = vec![None; values.len()];
+
+    for (i, &value) in values.iter().enumerate() {
+        let len = tails.partition_point(|&t| values[t] < value);
+        if len > 0 {
+            prev[i] = Some(tails[len - 1]);
+        }
+        if len == tails.len() {
+            tails.push(i);
+        } else {
+            tails[len] = i;
+        }
+    }
+
+    let mut run = HashSet::with_capacity(tails.len());
+    let mut current = tails.last().copied();
+    while let Some(i) = current {
+        run.insert(i);
+        current = prev[i];
+    }
+    run
+}
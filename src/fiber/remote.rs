@@ -0,0 +1,85 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering},
+    mpsc::{self, Receiver, Sender},
+};
+
+use crate::fiber::batch;
+
+type RemoteUpdate = Box<dyn FnOnce() + Send>;
+
+thread_local! {
+    static REMOTE_QUEUE: RemoteQueue = RemoteQueue::new();
+}
+
+/// Updates sent from other threads to the fibers of this thread.
+struct RemoteQueue {
+    sender: RemoteSender,
+    receiver: Receiver<RemoteUpdate>,
+}
+
+impl RemoteQueue {
+    fn new() -> Self {
+        let (sender, receiver) = mpsc::channel();
+        RemoteQueue {
+            sender: RemoteSender {
+                sender,
+                pending: Arc::new(AtomicUsize::new(0)),
+            },
+            receiver,
+        }
+    }
+}
+
+/// Sends updates to the remote queue of a thread.
+#[derive(Clone)]
+pub(crate) struct RemoteSender {
+    sender: Sender<RemoteUpdate>,
+    /// Number of updates sent but not processed yet.
+    pending: Arc<AtomicUsize>,
+}
+
+impl RemoteSender {
+    /// The sender of the current thread's queue.
+    pub(crate) fn current() -> Self {
+        REMOTE_QUEUE.with(|queue| queue.sender.clone())
+    }
+
+    pub(crate) fn send(&self, update: RemoteUpdate) {
+        self.pending.fetch_add(1, Ordering::Relaxed);
+        // The thread is gone with its fibers, there's nothing left to update.
+        if self.sender.send(update).is_err() {
+            self.pending.fetch_sub(1, Ordering::Relaxed);
+        }
+    }
+
+    pub(crate) fn has_pending(&self) -> bool {
+        self.pending.load(Ordering::Relaxed) > 0
+    }
+}
+
+/// Applies the updates sent to this thread's fibers by
+/// [remote setters](crate::SetStateAction::remote), in the order they were
+/// sent, as a single [`batch`].
+///
+/// Updates to fibers that were unmounted since are dropped, even if another
+/// fiber was mounted with the same id. Returns the number of updates received,
+/// dropped ones included.
+pub fn process_remote_updates() -> usize {
+    let updates: Vec<RemoteUpdate> = REMOTE_QUEUE.with(|queue| {
+        let updates: Vec<_> = queue.receiver.try_iter().collect();
+        queue
+            .sender
+            .pending
+            .fetch_sub(updates.len(), Ordering::Relaxed);
+        updates
+    });
+
+    let count = updates.len();
+    batch(|| {
+        for update in updates {
+            update();
+        }
+    });
+    count
+}
@@ -0,0 +1,104 @@
+use std::collections::HashMap;
+
+use crate::{FiberStoreError, fiber::FiberTree};
+
+impl FiberTree {
+    /// Move the fiber `id` and its descendants under `new_parent`, at `index`
+    /// among its children, or last.
+    ///
+    /// Path-based ids, i.e. prefixed with the id of the parent and a `/`, are
+    /// rewritten for the new parent. Returns the new id of the fiber.
+    pub fn move_fiber(
+        &mut self,
+        id: &str,
+        new_parent: Option<String>,
+        index: Option<usize>,
+    ) -> Result<String, FiberStoreError> {
+        let node = self
+            .0
+            .get(id)
+            .ok_or_else(|| FiberStoreError::FiberDoesntExist(id.to_string()))?;
+        let old_parent = node.parent.clone();
+
+        if let Some(new_parent) = &new_parent {
+            if !self.0.contains_key(new_parent) {
+                return Err(FiberStoreError::ParentDoesNotExist(new_parent.clone()));
+            }
+            let mut ancestor = Some(new_parent.as_str());
+            while let Some(current) = ancestor {
+                if current == id {
+                    return Err(FiberStoreError::MoveIntoDescendant(id.to_string()));
+                }
+                ancestor = self.0[current].parent.as_deref();
+            }
+        }
+
+        let new_id = match old_parent.as_deref().and_then(|p| relative_id(id, p)) {
+            Some(key) => match &new_parent {
+                Some(new_parent) => format!("{new_parent}/{key}"),
+                None => key.to_string(),
+            },
+            None => id.to_string(),
+        };
+
+        // New ids of the subtree, checked for collisions before anything changes.
+        let mut renames = HashMap::new();
+        let mut stack = vec![id.to_string()];
+        while let Some(current) = stack.pop() {
+            let renamed = match relative_id(&current, id) {
+                Some(rest) if new_id != id => format!("{new_id}/{rest}"),
+                _ if current == id => new_id.clone(),
+                _ => current.clone(),
+            };
+            stack.extend(self.0[&current].children.iter().cloned());
+            renames.insert(current, renamed);
+        }
+        for renamed in renames.values() {
+            if !renames.contains_key(renamed) && self.0.contains_key(renamed) {
+                return Err(FiberStoreError::FiberAlreadyExists(renamed.clone()));
+            }
+        }
+
+        if let Some(old_parent) = &old_parent
+            && let Some(old_parent) = self.0.get_mut(old_parent)
+        {
+            old_parent.children.retain(|c| c != id);
+        }
+
+        let nodes: Vec<_> = renames
+            .keys()
+            .map(|current| (current.clone(), self.0.remove(current).expect("in subtree")))
+            .collect();
+        for (current, mut node) in nodes {
+            node.parent = if current == id {
+                new_parent.clone()
+            } else {
+                node.parent.map(|p| renames[&p].clone())
+            };
+            for child in &mut node.children {
+                *child = renames[child].clone();
+            }
+
+            let state_ptr = node.fiber.borrow_mut().state_ptr_mut();
+            let state = unsafe { &mut *state_ptr };
+            state.fiber_id = renames[&current].clone();
+            // The providers above the subtree changed.
+            state.invalidate_context();
+
+            self.0.insert(renames[&current].clone(), node);
+        }
+
+        if let Some(new_parent) = &new_parent {
+            let children = &mut self.0.get_mut(new_parent).expect("checked above").children;
+            let index = index.map_or(children.len(), |i| i.min(children.len()));
+            children.insert(index, new_id.clone());
+        }
+
+        Ok(new_id)
+    }
+}
+
+/// The part of `id` after `parent/`, if `id` is path-based.
+fn relative_id<'a>(id: &'a str, parent: &str) -> Option<&'a str> {
+    id.strip_prefix(parent)?.strip_prefix('/')
+}
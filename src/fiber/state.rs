@@ -1,15 +1,255 @@
+use std::{
+    cell::RefCell,
+    collections::HashSet,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
 use crate::hooks::Hook;
 
+static NEXT_FIBER_UID: AtomicU64 = AtomicU64::new(1);
+
+thread_local! {
+    /// Uids of the hooks states alive on this thread.
+    static LIVE_UIDS: RefCell<HashSet<u64>> = RefCell::new(HashSet::new());
+}
+
 pub struct HooksState {
+    /// Id of the fiber owning this state.
+    pub(crate) fiber_id: String,
+    /// Unique to this mount of the fiber, unlike the id which can be reused.
+    pub(crate) uid: u64,
     pub hooks: Vec<Hook>,
     pub hook_index: usize,
+    /// Set by state updates, cleared when the fiber renders.
+    pub(crate) dirty: bool,
+    /// Indices of the hooks whose updates made the fiber dirty.
+    pub(crate) dirty_hooks: Vec<usize>,
+    /// Contexts read during the current render, as (context id, provider version).
+    pub(crate) context_reads: Vec<(u64, u64)>,
+    /// Contexts read during the previous render.
+    pub(crate) prev_context_reads: Vec<(u64, u64)>,
+    /// Number of times the fiber rendered.
+    pub(crate) render_count: u64,
+    /// Number of times the fiber's effects ran.
+    pub(crate) effect_count: u64,
+    /// Hook kinds recorded after the first render, used to verify the hook order.
+    #[cfg(any(debug_assertions, feature = "verify-hooks"))]
+    pub(crate) layout: Option<Vec<&'static str>>,
+    /// Slots that held a hook of another type during this render, as (index,
+    /// type held, type requested), recorded by the slot lookup.
+    #[cfg(any(debug_assertions, feature = "verify-hooks"))]
+    pub(crate) mismatches: Vec<(usize, &'static str, &'static str)>,
+    /// Number of hooks before the fiber's function was replaced, until its next render.
+    pub(crate) reload: Option<usize>,
+    /// Hooks replaced during the render following a function swap, because the
+    /// new function called a different hook at their slot.
+    pub(crate) replaced: Vec<Hook>,
+    /// Set when the last render reset the state after a function swap.
+    pub(crate) reset: bool,
+    /// Values set by [`restore`](crate::restore), by hook index, consumed on mount.
+    #[cfg(feature = "serde")]
+    pub(crate) hydration: std::collections::HashMap<usize, serde_json::Value>,
+    /// Report of the [`restore`](crate::restore) call that set `hydration`.
+    #[cfg(feature = "serde")]
+    pub(crate) hydration_report: Option<crate::RestoreReport>,
 }
 
 impl HooksState {
-    pub(crate) fn new() -> Self {
+    pub(crate) fn new(fiber_id: String) -> Self {
         Self {
+            fiber_id,
+            uid: register_uid(),
             hooks: Vec::new(),
             hook_index: 0,
+            dirty: false,
+            dirty_hooks: Vec::new(),
+            context_reads: Vec::new(),
+            prev_context_reads: Vec::new(),
+            render_count: 0,
+            effect_count: 0,
+            #[cfg(any(debug_assertions, feature = "verify-hooks"))]
+            layout: None,
+            #[cfg(any(debug_assertions, feature = "verify-hooks"))]
+            mismatches: Vec::new(),
+            reload: None,
+            replaced: Vec::new(),
+            reset: false,
+            #[cfg(feature = "serde")]
+            hydration: std::collections::HashMap::new(),
+            #[cfg(feature = "serde")]
+            hydration_report: None,
+        }
+    }
+
+    /// Whether the state with this uid still exists, i.e. its fiber wasn't
+    /// unmounted. Pointers to a live state are valid, since it is boxed.
+    pub(crate) fn is_live(uid: u64) -> bool {
+        LIVE_UIDS.with(|live| live.borrow().contains(&uid))
+    }
+
+    /// Tears down the hooks of an unmounted fiber, see [`finalize_hooks`].
+    pub(crate) fn unmount(&mut self) {
+        finalize_hooks(&mut self.hooks);
+        self.hook_index = 0;
+    }
+
+    /// Marks the fiber dirty because of an update to the hook at `hook_index`.
+    pub(crate) fn mark_dirty(&mut self, hook_index: usize) {
+        self.dirty = true;
+        if !self.dirty_hooks.contains(&hook_index) {
+            self.dirty_hooks.push(hook_index);
+        }
+    }
+
+    /// Marks the fiber dirty and forgets the contexts it read, e.g. after it
+    /// moved under other providers.
+    pub(crate) fn invalidate_context(&mut self) {
+        self.dirty = true;
+        self.context_reads.clear();
+    }
+
+    /// Resets the per-render bookkeeping before the fiber renders.
+    pub(crate) fn begin_render(&mut self) {
+        self.hook_index = 0;
+        self.dirty = false;
+        self.dirty_hooks.clear();
+        self.reset = false;
+        #[cfg(any(debug_assertions, feature = "verify-hooks"))]
+        self.mismatches.clear();
+        self.render_count += 1;
+        self.prev_context_reads = std::mem::take(&mut self.context_reads);
+    }
+
+    /// Starts checking the hook layout on the next render, after the fiber's function was replaced.
+    pub(crate) fn begin_reload(&mut self) {
+        if !self.hooks.is_empty() {
+            self.reload = Some(self.hooks.len());
+        }
+    }
+
+    /// Ends the first render after a function swap.
+    ///
+    /// Returns whether the new function called the same hooks in the same
+    /// order. If it didn't, every hook is unmounted and the state reset, and
+    /// the fiber is marked dirty so it renders again from scratch. The state
+    /// gets a new uid, as for a new mount.
+    pub(crate) fn finish_reload(&mut self) -> bool {
+        let Some(hook_count) = self.reload.take() else {
+            return true;
+        };
+
+        let compatible = self.replaced.is_empty()
+            && self.hook_index == hook_count
+            && self.hooks.len() == hook_count;
+
+        if compatible {
+            return true;
+        }
+
+        let mut hooks = std::mem::take(&mut self.replaced);
+        hooks.append(&mut self.hooks);
+        finalize_hooks(&mut hooks);
+
+        // Setters and slot refs of the old hooks must not reach the new ones.
+        LIVE_UIDS.with(|live| live.borrow_mut().remove(&self.uid));
+        self.uid = register_uid();
+
+        self.hook_index = 0;
+        self.dirty = true;
+        self.reset = true;
+        #[cfg(any(debug_assertions, feature = "verify-hooks"))]
+        {
+            self.layout = None;
+        }
+        false
+    }
+
+    /// Runs the `on_commit` callbacks of the hooks called during the last render.
+    pub(crate) fn commit(&mut self) {
+        for hook in &mut self.hooks[..self.hook_index] {
+            if let Some(on_commit) = &mut hook.on_commit {
+                on_commit(&mut *hook.state);
+            }
+        }
+    }
+
+    /// Compares the hooks called during the render that just finished with the
+    /// layout recorded after the first render.
+    ///
+    /// The first call records the layout. Later calls return a description of
+    /// every slot that was skipped, added or changed kind, or whose hook was
+    /// called with another type.
+    #[cfg(any(debug_assertions, feature = "verify-hooks"))]
+    pub(crate) fn verify_hook_order(&mut self) -> Result<(), String> {
+        let called: Vec<&'static str> = self.hooks[..self.hook_index]
+            .iter()
+            .map(|h| h.kind)
+            .collect();
+
+        let mismatches = std::mem::take(&mut self.mismatches);
+        let Some(layout) = &self.layout else {
+            self.layout = Some(called);
+            return Ok(());
+        };
+
+        if *layout == called && mismatches.is_empty() {
+            return Ok(());
+        }
+
+        let mut diff = format!(
+            "expected {} hook(s) as in the first render, but {} were called:",
+            layout.len(),
+            called.len()
+        );
+        for slot in 0..layout.len().max(called.len()) {
+            match (layout.get(slot), called.get(slot)) {
+                (Some(expected), Some(got)) if expected != got => {
+                    diff += &format!("\n  slot {slot}: expected `{expected}`, got `{got}`");
+                }
+                // Same kind of hook, holding another type.
+                (Some(kind), Some(_)) => {
+                    if let Some((_, held, requested)) = mismatches.iter().find(|m| m.0 == slot) {
+                        diff += &format!(
+                            "\n  slot {slot}: expected `{kind}` of `{held}`, got `{requested}`"
+                        );
+                    }
+                }
+                (Some(expected), None) => {
+                    diff += &format!("\n  slot {slot}: `{expected}` was skipped");
+                }
+                (None, Some(got)) => {
+                    diff += &format!("\n  slot {slot}: `{got}` was added");
+                }
+                _ => {}
+            }
+        }
+
+        Err(diff)
+    }
+}
+
+impl Drop for HooksState {
+    fn drop(&mut self) {
+        // The registry may already be gone when the thread exits.
+        let _ = LIVE_UIDS.try_with(|live| live.borrow_mut().remove(&self.uid));
+    }
+}
+
+/// Allocates a uid for a hooks state and marks it live.
+fn register_uid() -> u64 {
+    let uid = NEXT_FIBER_UID.fetch_add(1, Ordering::Relaxed);
+    LIVE_UIDS.with(|live| live.borrow_mut().insert(uid));
+    uid
+}
+
+/// Runs the `on_unmount` callback of each hook and drops it, last hook first.
+///
+/// Like local variables, later hooks may depend on earlier ones, so the earlier
+/// ones are still there while a later hook is torn down.
+fn finalize_hooks(hooks: &mut Vec<Hook>) {
+    while let Some(mut hook) = hooks.pop() {
+        if let Some(on_unmount) = hook.on_unmount.take() {
+            on_unmount(&mut *hook.state);
         }
     }
 }
@@ -2,16 +2,20 @@ use std::{cell::RefCell, collections::HashMap, rc::Rc};
 
 use crate::{
     error::FiberStoreError,
-    fiber::{ErasedFiber, Fiber},
+    fiber::{ErasedFiber, Fiber, FiberObserver, ObserverId, notify},
 };
 
 thread_local! {
     pub static FIBER_TREE: RefCell<FiberTree> = RefCell::new(FiberTree::new());
-    pub(crate) static CURRENT_FIBER_ID: RefCell<Option<String>> = RefCell::new(None);
+    pub(crate) static CURRENT_FIBER_ID: RefCell<Option<String>> = const { RefCell::new(None) };
 }
 
-/// A tree of fibers where each node can have children.
-pub struct FiberTree(pub(crate) HashMap<String, FiberNode>);
+/// A tree of fibers where each node can have children, and the observers of
+/// their lifecycle.
+pub struct FiberTree(
+    pub(crate) HashMap<String, FiberNode>,
+    pub(crate) Vec<(ObserverId, Rc<dyn FiberObserver>)>,
+);
 
 pub(crate) struct FiberNode {
     pub(crate) fiber: Rc<RefCell<Box<dyn ErasedFiber>>>,
@@ -22,7 +26,7 @@ pub(crate) struct FiberNode {
 impl FiberTree {
     /// Create a new empty fiber tree node.
     pub fn new() -> Self {
-        Self(HashMap::new())
+        Self(HashMap::new(), Vec::new())
     }
 
     /// Mount a fiber under a parent node.
@@ -30,7 +34,22 @@ impl FiberTree {
         &mut self,
         parent: Option<String>,
         id: String,
-        fun: fn(P) -> R,
+        fun: impl FnMut(P) -> R + 'static,
+    ) -> Result<(), FiberStoreError>
+    where
+        P: 'static,
+        R: 'static,
+    {
+        self.insert_fiber(parent, id, None, fun)
+    }
+
+    /// Mount a fiber at `index` among the children of `parent`, or last.
+    pub(crate) fn insert_fiber<P, R>(
+        &mut self,
+        parent: Option<String>,
+        id: String,
+        index: Option<usize>,
+        fun: impl FnMut(P) -> R + 'static,
     ) -> Result<(), FiberStoreError>
     where
         P: 'static,
@@ -44,7 +63,7 @@ impl FiberTree {
         self.0.insert(
             id.clone(),
             FiberNode {
-                fiber: Rc::new(RefCell::new(Box::new(Fiber::new(fun)))),
+                fiber: Rc::new(RefCell::new(Box::new(Fiber::new(id.clone(), fun)))),
                 parent: parent.clone(),
                 children: Vec::new(),
             },
@@ -52,7 +71,8 @@ impl FiberTree {
 
         if let Some(parent) = parent {
             if let Some(parent) = self.0.get_mut(&parent.to_string()) {
-                parent.children.push(id);
+                let index = index.map_or(parent.children.len(), |i| i.min(parent.children.len()));
+                parent.children.insert(index, id);
             } else {
                 return Err(FiberStoreError::ParentDoesNotExist(parent.to_string()));
             }
@@ -61,18 +81,89 @@ impl FiberTree {
         Ok(())
     }
 
-    /// Unmount a fiber and all its descendants
-    pub fn unmount_fiber(&mut self, id: String) {
-        if let Some(node) = self.0.remove(&id) {
-            for child in node.children {
-                self.unmount_fiber(child);
+    /// Swap the function of a mounted fiber, keeping its hooks state.
+    pub fn replace_fiber_fn<P, R>(
+        &mut self,
+        id: String,
+        fun: impl FnMut(P) -> R + 'static,
+    ) -> Result<(), FiberStoreError>
+    where
+        P: 'static,
+        R: 'static,
+    {
+        let node = self
+            .0
+            .get(&id)
+            .ok_or_else(|| FiberStoreError::FiberDoesntExist(id.clone()))?;
+        let mut fiber = node.fiber.borrow_mut();
+
+        if let Some(fiber) = fiber.as_any_mut().downcast_mut::<Fiber<P, R>>() {
+            fiber.fun = Box::new(fun);
+            fiber.state.begin_reload();
+            return Ok(());
+        }
+
+        // Different props or output type: move the state over to a new fiber.
+        let old = std::mem::replace(&mut *fiber, Box::new(Fiber::new(id.clone(), fun)));
+        let mut state = old.into_state();
+        state.begin_reload();
+
+        let fiber = fiber
+            .as_any_mut()
+            .downcast_mut::<Fiber<P, R>>()
+            .expect("fiber was just created");
+        fiber.state = state;
+        Ok(())
+    }
+
+    /// Remove a fiber and all its descendants from the tree.
+    ///
+    /// The nodes are returned in teardown order, children before their parent.
+    /// No teardown is run here, so it can happen once the tree is no longer borrowed.
+    pub(crate) fn detach_fiber(&mut self, id: String) -> Vec<FiberNode> {
+        let mut detached = Vec::new();
+        self.detach_fiber_impl(id, &mut detached);
+        detached
+    }
+
+    fn detach_fiber_impl(&mut self, id: String, detached: &mut Vec<FiberNode>) {
+        if let Some(mut node) = self.0.remove(&id) {
+            for child in std::mem::take(&mut node.children) {
+                self.detach_fiber_impl(child, detached);
             }
 
-            if let Some(parent) = node.parent {
-                if let Some(parent) = self.0.get_mut(&parent) {
-                    parent.children.retain(|c| c != &id);
-                }
+            if let Some(parent) = &node.parent
+                && let Some(parent) = self.0.get_mut(parent)
+            {
+                parent.children.retain(|c| c != &id);
             }
+
+            detached.push(node);
         }
     }
 }
+
+impl FiberNode {
+    pub(crate) fn is_dirty(&self) -> bool {
+        let state_ptr = self.fiber.borrow().state_ptr();
+        unsafe { (*state_ptr).dirty }
+    }
+
+    pub(crate) fn was_reset(&self) -> bool {
+        let state_ptr = self.fiber.borrow().state_ptr();
+        unsafe { (*state_ptr).reset }
+    }
+
+    /// Tear down the hooks of a detached fiber.
+    pub(crate) fn unmount(self) {
+        let state_ptr = self.fiber.borrow_mut().state_ptr_mut();
+        let state = unsafe { &mut *state_ptr };
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(fiber = %state.fiber_id, "unmount");
+
+        state.unmount();
+
+        notify(|observer| observer.on_unmount(&state.fiber_id));
+    }
+}
@@ -0,0 +1,239 @@
+use std::{
+    any::Any,
+    cell::{Cell, RefCell},
+    marker::PhantomData,
+    rc::Rc,
+};
+
+use crate::fiber::{FIBER_TREE, HooksState};
+
+/// Writes a recorded value back into a type-erased hook state.
+pub(crate) type RestoreFn = fn(&mut dyn Any, &dyn Any) -> bool;
+
+thread_local! {
+    static HISTORY: RefCell<Option<Timeline>> = const { RefCell::new(None) };
+    static NEXT_HISTORY_ID: Cell<u64> = const { Cell::new(0) };
+}
+
+struct Timeline {
+    /// Id of the [`History`] handle recording this timeline.
+    id: u64,
+    entries: Vec<HistoryEntry>,
+    /// Number of entries currently applied, the rest can be redone.
+    position: usize,
+}
+
+/// A state change recorded by [`History`].
+#[derive(Clone)]
+pub struct HistoryEntry {
+    pub fiber_id: String,
+    pub hook_index: usize,
+    /// Uid of the fiber's state, so a fiber remounted with the same id is left alone.
+    uid: u64,
+    old: Rc<dyn Any>,
+    new: Rc<dyn Any>,
+    restore: RestoreFn,
+}
+
+impl HistoryEntry {
+    /// The value before the change, if the state is an `S`.
+    pub fn old_value<S: 'static>(&self) -> Option<&S> {
+        self.old.downcast_ref()
+    }
+
+    /// The value after the change, if the state is an `S`.
+    pub fn new_value<S: 'static>(&self) -> Option<&S> {
+        self.new.downcast_ref()
+    }
+}
+
+/// Records the changes of every `use_state` hook on the current thread, so
+/// they can be undone and redone.
+///
+/// A change is recorded when it is applied to the hook, i.e. on the next
+/// render for queued updates. Undoing and redoing write the recorded values
+/// back into the hooks and mark their fibers dirty.
+///
+/// Recording stops when the handle is dropped.
+///
+/// ```rust
+/// use hooks_rs::{History, call_fiber, mount_fiber, use_state};
+///
+/// fn counter(_: ()) -> i32 {
+///     let (count, set_count) = use_state(|| 0);
+///     set_count.update(|prev| prev + 1);
+///     count
+/// }
+///
+/// mount_fiber(None, "counter", counter).unwrap();
+/// let history = History::start();
+///
+/// call_fiber::<(), i32>("counter", ()).unwrap();
+/// assert_eq!(call_fiber::<(), i32>("counter", ()).unwrap(), 1);
+/// assert_eq!(call_fiber::<(), i32>("counter", ()).unwrap(), 2);
+///
+/// history.undo();
+/// assert_eq!(call_fiber::<(), i32>("counter", ()).unwrap(), 1);
+/// ```
+pub struct History {
+    id: u64,
+    _not_send: PhantomData<*const ()>,
+}
+
+impl History {
+    /// Starts recording, dropping any history recorded so far.
+    pub fn start() -> History {
+        let id = NEXT_HISTORY_ID.with(|next| next.replace(next.get() + 1));
+        let timeline = Timeline {
+            id,
+            entries: Vec::new(),
+            position: 0,
+        };
+        HISTORY.with(|h| *h.borrow_mut() = Some(timeline));
+        History {
+            id,
+            _not_send: PhantomData,
+        }
+    }
+
+    /// Stops recording and drops the history, like dropping the handle.
+    pub fn stop(self) {
+        drop(self);
+    }
+
+    /// Whether changes are being recorded on the current thread.
+    pub fn is_recording() -> bool {
+        HISTORY.with(|h| h.borrow().is_some())
+    }
+
+    /// Every recorded change, oldest first, including the undone ones.
+    pub fn entries(&self) -> Vec<HistoryEntry> {
+        self.with_timeline(|timeline| timeline.entries.clone())
+    }
+
+    /// Number of changes currently applied.
+    pub fn position(&self) -> usize {
+        self.with_timeline(|timeline| timeline.position)
+    }
+
+    /// Reverts the last applied change. Returns `false` if there was none.
+    pub fn undo(&self) -> bool {
+        let position = self.position();
+        if position == 0 {
+            return false;
+        }
+        self.jump_to(position - 1);
+        true
+    }
+
+    /// Applies the last undone change again. Returns `false` if there was none.
+    pub fn redo(&self) -> bool {
+        let (position, len) = self.with_timeline(|t| (t.position, t.entries.len()));
+        if position == len {
+            return false;
+        }
+        self.jump_to(position + 1);
+        true
+    }
+
+    /// Undoes or redoes changes until exactly `position` of them are applied.
+    ///
+    /// Changes of fibers that were unmounted since are skipped, even if a
+    /// fiber with the same id was mounted again.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `position` is greater than the number of recorded changes.
+    pub fn jump_to(&self, position: usize) {
+        self.with_timeline(|timeline| {
+            assert!(
+                position <= timeline.entries.len(),
+                "Can't jump to change {position}, only {} were recorded.",
+                timeline.entries.len()
+            );
+
+            while timeline.position > position {
+                timeline.position -= 1;
+                let entry = &timeline.entries[timeline.position];
+                write_back(entry, &*entry.old);
+            }
+            while timeline.position < position {
+                let entry = &timeline.entries[timeline.position];
+                write_back(entry, &*entry.new);
+                timeline.position += 1;
+            }
+        });
+    }
+
+    fn with_timeline<T>(&self, f: impl FnOnce(&mut Timeline) -> T) -> T {
+        HISTORY.with(|h| {
+            let mut history = h.borrow_mut();
+            let timeline = history.as_mut().filter(|timeline| timeline.id == self.id);
+            f(timeline.expect("History recording was stopped."))
+        })
+    }
+}
+
+impl Drop for History {
+    fn drop(&mut self) {
+        HISTORY.with(|h| {
+            let mut history = h.borrow_mut();
+            // A newer recording isn't stopped by an older handle.
+            if history
+                .as_ref()
+                .is_some_and(|timeline| timeline.id == self.id)
+            {
+                *history = None;
+            }
+        });
+    }
+}
+
+fn write_back(entry: &HistoryEntry, value: &dyn Any) {
+    FIBER_TREE.with(|t| {
+        let tree = t.borrow();
+        let Some(node) = tree.0.get(&entry.fiber_id) else {
+            return;
+        };
+        let state_ptr = node.fiber.borrow_mut().state_ptr_mut();
+        let state = unsafe { &mut *state_ptr };
+        if state.uid != entry.uid {
+            return;
+        }
+
+        let Some(hook) = state.hooks.get_mut(entry.hook_index) else {
+            return;
+        };
+        if (entry.restore)(&mut *hook.state, value) {
+            state.mark_dirty(entry.hook_index);
+        }
+    });
+}
+
+/// Records a state change, if the recorder is on.
+pub(crate) fn record<S: Clone + 'static>(
+    fiber: *const HooksState,
+    hook_index: usize,
+    old: &S,
+    new: &S,
+    restore: RestoreFn,
+) {
+    HISTORY.with(|h| {
+        let mut history = h.borrow_mut();
+        let Some(timeline) = history.as_mut() else {
+            return;
+        };
+
+        // A new change drops the undone ones.
+        timeline.entries.truncate(timeline.position);
+        timeline.entries.push(HistoryEntry {
+            fiber_id: unsafe { (*fiber).fiber_id.clone() },
+            hook_index,
+            uid: unsafe { (*fiber).uid },
+            old: Rc::new(old.clone()),
+            new: Rc::new(new.clone()),
+            restore,
+        });
+        timeline.position = timeline.entries.len();
+    });
+}
@@ -4,12 +4,45 @@ pub mod use_effect;
 pub mod use_ref;
 pub mod use_state;
 
+// Custom hooks
+pub mod slot;
+
 /// Internal Hooks enum
 use std::any::{Any, TypeId};
 
+type OnUnmount = Box<dyn FnOnce(&mut dyn Any)>;
+type OnCommit = Box<dyn FnMut(&mut dyn Any)>;
+
 pub struct Hook {
     pub type_id: TypeId,
+    /// Human readable hook kind (e.g. `use_state`), used in diagnostics.
+    pub kind: &'static str,
     pub state: Box<dyn Any>,
+    /// Type of the value held by the hook, shown by [`inspect`](crate::inspect).
+    pub(crate) type_name: &'static str,
+    /// Formats the hook's value for [`inspect`](crate::inspect).
+    pub(crate) debug: Option<crate::inspect::DebugFn>,
+    pub(crate) on_unmount: Option<OnUnmount>,
+    pub(crate) on_commit: Option<OnCommit>,
+    /// Serializes the hook's state for [`snapshot`](crate::snapshot).
+    #[cfg(feature = "serde")]
+    pub(crate) serialize: Option<crate::snapshot::SerializeFn>,
+}
+
+impl Hook {
+    pub fn new<T: 'static>(kind: &'static str, state: T) -> Self {
+        Self {
+            type_id: TypeId::of::<T>(),
+            kind,
+            state: Box::new(state),
+            type_name: std::any::type_name::<T>(),
+            debug: None,
+            on_unmount: None,
+            on_commit: None,
+            #[cfg(feature = "serde")]
+            serialize: None,
+        }
+    }
 }
 
 use crate::fiber::{CURRENT_FIBER_ID, FIBER_TREE, HooksState};
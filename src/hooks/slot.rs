@@ -0,0 +1,302 @@
+use std::{
+    any::{Any, TypeId, type_name},
+    cell::{Ref, RefCell, RefMut},
+    fmt::Debug,
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+    panic::Location,
+    rc::Rc,
+};
+
+use crate::{
+    fiber::HooksState,
+    hooks::{Hook, read_fiber_state},
+    inspect::DebugFn,
+};
+
+/// Claims the next hook slot of the current fiber, for writing custom hooks.
+///
+/// On mount, `init` creates the slot's state. On later renders the stored
+/// state is returned, after checking it is still a `T` (i.e. the hook order
+/// didn't change).
+///
+/// # Panics
+///
+/// Panics if called outside of a fiber, or if the slot at this index holds a
+/// different kind of hook. When the hook order is verified (see
+/// `verify-hooks`), the latter is reported at the end of the render instead,
+/// with every other slot that changed.
+///
+/// # Examples
+///
+/// ```rust
+/// use hooks_rs::{call_fiber, mount_fiber, use_hook_slot};
+///
+/// /// Counts how many times the component rendered.
+/// fn use_render_count() -> u32 {
+///     let count = use_hook_slot(|| 0u32);
+///     *count.borrow_mut() += 1;
+///     *count.borrow()
+/// }
+///
+/// fn component(_: ()) -> u32 {
+///     use_render_count()
+/// }
+///
+/// mount_fiber(None, "root", component).unwrap();
+///
+/// assert_eq!(call_fiber::<(), u32>("root", ()).unwrap(), 1);
+/// assert_eq!(call_fiber::<(), u32>("root", ()).unwrap(), 2);
+/// ```
+#[track_caller]
+pub fn use_hook_slot<T: 'static>(init: impl FnOnce() -> T) -> HookSlotRef<T> {
+    let slot = hook_slot(type_name::<T>(), type_name::<T>(), || {
+        Rc::new(RefCell::new(init()))
+    });
+    HookSlotRef {
+        state: Rc::clone(&slot),
+        slot,
+    }
+}
+
+/// [`use_hook_slot`] with a custom hook kind used in diagnostics, and the
+/// type of the value the hook holds.
+#[track_caller]
+pub(crate) fn hook_slot<T: 'static>(
+    kind: &'static str,
+    value_type: &'static str,
+    init: impl FnOnce() -> T,
+) -> SlotRef<T> {
+    let location = Location::caller();
+
+    let fiber_state = read_fiber_state(&format!(
+        "Hook `{kind}` was called outside of a fiber. ({location})"
+    ));
+
+    let idx = fiber_state.hook_index;
+    fiber_state.hook_index += 1;
+
+    let mounted = if idx >= fiber_state.hooks.len() {
+        // MOUNT LOGIC HERE
+        fiber_state.hooks.push(Hook {
+            type_name: value_type,
+            ..Hook::new(kind, init())
+        });
+        true
+    } else if fiber_state.hooks[idx].type_id != TypeId::of::<T>() {
+        // Right after a function swap the slot is replaced, and the whole
+        // state reset once the render is over. When the hook order is
+        // verified, the slot is replaced too and the mismatch recorded, so
+        // the render goes on and the end of render check reports every slot.
+        if fiber_state.reload.is_none() {
+            #[cfg(not(any(debug_assertions, feature = "verify-hooks")))]
+            panic!(
+                "Expected `{kind}` hook at slot {idx}, but got `{}`. ({location})",
+                fiber_state.hooks[idx].kind
+            );
+            #[cfg(any(debug_assertions, feature = "verify-hooks"))]
+            fiber_state
+                .mismatches
+                .push((idx, fiber_state.hooks[idx].type_name, value_type));
+        }
+        let hook = Hook {
+            type_name: value_type,
+            ..Hook::new(kind, init())
+        };
+        let replaced = std::mem::replace(&mut fiber_state.hooks[idx], hook);
+        fiber_state.replaced.push(replaced);
+        true
+    } else {
+        // UPDATE LOGIC HERE
+        false
+    };
+
+    #[cfg(feature = "tracing")]
+    tracing::trace!(
+        fiber = %fiber_state.fiber_id,
+        index = idx,
+        kind,
+        mounted,
+        "hook"
+    );
+
+    SlotRef {
+        uid: fiber_state.uid,
+        fiber_ptr: fiber_state,
+        index: idx,
+        mounted,
+        _marker: PhantomData,
+    }
+}
+
+/// Handle to the state of a hook slot, returned by [`use_hook_slot`].
+///
+/// The state is kept in a `RefCell`, so borrows are checked even if the
+/// handle outlives the render that returned it.
+///
+/// # Panics
+///
+/// Using the handle panics once its fiber has been unmounted.
+pub struct HookSlotRef<T> {
+    state: Rc<RefCell<T>>,
+    slot: SlotRef<Rc<RefCell<T>>>,
+}
+
+impl<T: 'static> HookSlotRef<T> {
+    /// Index of the slot in the fiber's hook list.
+    pub fn index(&self) -> usize {
+        self.slot.index
+    }
+
+    /// Whether the slot was created during this render.
+    pub fn is_mount(&self) -> bool {
+        self.slot.mounted
+    }
+
+    /// Borrows the slot's state.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the state is mutably borrowed.
+    pub fn borrow(&self) -> Ref<'_, T> {
+        self.slot.check_live();
+        self.state.borrow()
+    }
+
+    /// Mutably borrows the slot's state.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the state is borrowed.
+    pub fn borrow_mut(&self) -> RefMut<'_, T> {
+        self.slot.check_live();
+        self.state.borrow_mut()
+    }
+
+    /// Registers a callback run with the slot's state when the fiber is unmounted.
+    ///
+    /// Replaces the previously registered callback.
+    pub fn on_unmount(&mut self, f: impl FnOnce(&mut T) + 'static) {
+        self.slot
+            .on_unmount(move |state| f(&mut state.borrow_mut()));
+    }
+
+    /// Registers a callback run with the slot's state after every render of the
+    /// fiber, once all of its hooks have been called.
+    ///
+    /// Replaces the previously registered callback.
+    pub fn on_commit(&mut self, mut f: impl FnMut(&mut T) + 'static) {
+        self.slot.on_commit(move |state| f(&mut state.borrow_mut()));
+    }
+
+    /// Shows the slot's value in [`inspect`](crate::inspect), formatted with `Debug`.
+    pub fn debug_value(&mut self)
+    where
+        T: Debug,
+    {
+        self.slot.set_debug(|state| {
+            let state = state.downcast_ref::<Rc<RefCell<T>>>().unwrap();
+            debug_cell(state)
+        });
+    }
+}
+
+/// Handle to a hook slot of the fiber being rendered, returned by [`hook_slot`].
+///
+/// Dereferences to the slot's state. Unlike [`HookSlotRef`], it doesn't stop
+/// the state from being aliased, so hooks only use it during the render that
+/// returned it.
+pub(crate) struct SlotRef<T> {
+    fiber_ptr: *mut HooksState,
+    /// Uid of the fiber state, checked before `fiber_ptr` is dereferenced.
+    uid: u64,
+    index: usize,
+    mounted: bool,
+    _marker: PhantomData<T>,
+}
+
+impl<T: 'static> SlotRef<T> {
+    /// Index of the slot in the fiber's hook list.
+    pub(crate) fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Whether the slot was created during this render.
+    pub(crate) fn is_mount(&self) -> bool {
+        self.mounted
+    }
+
+    /// Registers a callback run with the slot's state when the fiber is unmounted.
+    pub(crate) fn on_unmount(&mut self, f: impl FnOnce(&mut T) + 'static) {
+        self.hook().on_unmount = Some(Box::new(move |state: &mut dyn Any| {
+            f(state.downcast_mut::<T>().expect("type checked on creation"))
+        }));
+    }
+
+    /// Registers a callback run with the slot's state after every render of the fiber.
+    pub(crate) fn on_commit(&mut self, mut f: impl FnMut(&mut T) + 'static) {
+        self.hook().on_commit = Some(Box::new(move |state: &mut dyn Any| {
+            f(state.downcast_mut::<T>().expect("type checked on creation"))
+        }));
+    }
+
+    /// Shows the slot's value in [`inspect`](crate::inspect), formatted by `debug`.
+    pub(crate) fn set_debug(&mut self, debug: DebugFn) {
+        self.hook().debug = Some(debug);
+    }
+
+    pub(crate) fn fiber_ptr(&self) -> *mut HooksState {
+        self.fiber_ptr
+    }
+
+    /// Uid of the fiber state the slot belongs to.
+    pub(crate) fn uid(&self) -> u64 {
+        self.uid
+    }
+
+    fn check_live(&self) {
+        assert!(
+            HooksState::is_live(self.uid),
+            "Hook slot {} was used after its fiber was unmounted.",
+            self.index
+        );
+    }
+
+    /// The slot's hook, after checking its fiber is still mounted.
+    fn hook(&mut self) -> &mut Hook {
+        self.check_live();
+        let fiber = unsafe { &mut *self.fiber_ptr };
+        &mut fiber.hooks[self.index]
+    }
+}
+
+impl<T: 'static> Deref for SlotRef<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.check_live();
+        let fiber = unsafe { &*self.fiber_ptr };
+        fiber.hooks[self.index]
+            .state
+            .downcast_ref::<T>()
+            .expect("type checked on creation")
+    }
+}
+
+impl<T: 'static> DerefMut for SlotRef<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.hook()
+            .state
+            .downcast_mut::<T>()
+            .expect("type checked on creation")
+    }
+}
+
+/// Formats the value of a cell for [`inspect`](crate::inspect), unless it is
+/// mutably borrowed.
+pub(crate) fn debug_cell<T: Debug>(cell: &RefCell<T>) -> String {
+    match cell.try_borrow() {
+        Ok(value) => format!("{:?}", *value),
+        Err(_) => "<borrowed>".to_string(),
+    }
+}
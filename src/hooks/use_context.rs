@@ -1,27 +1,31 @@
 use std::{
-    any::TypeId,
-    intrinsics::caller_location,
+    any::{TypeId, type_name},
+    fmt::Debug,
     marker::PhantomData,
+    panic::Location,
     sync::atomic::{AtomicU64, Ordering},
 };
 
 use crate::{
     fiber::{CURRENT_FIBER_ID, FIBER_TREE, get_parent_id},
-    hooks::{Hook, read_fiber_state},
+    hooks::{
+        read_fiber_state,
+        slot::{SlotRef, hook_slot},
+    },
 };
 
 static NEXT_CONTEXT_ID: AtomicU64 = AtomicU64::new(1);
+static NEXT_PROVIDER_VERSION: AtomicU64 = AtomicU64::new(1);
 
 pub struct Context<T> {
     id: u64,
-    _marker: PhantomData<T>,
+    /// Contexts only identify a value, they don't hold one, so they are
+    /// `Send` and `Sync` whatever `T` is and can be kept in statics.
+    _marker: PhantomData<fn() -> T>,
 }
 impl<T> Clone for Context<T> {
     fn clone(&self) -> Self {
-        Self {
-            id: self.id,
-            _marker: PhantomData,
-        }
+        *self
     }
 }
 impl<T> Copy for Context<T> {}
@@ -42,49 +46,75 @@ where
 {
     ctx_id: u64,
     value: T,
+    /// Changes every time the provider renders, or with [`provide_context_eq`]
+    /// every time the provided value changes, so readers can tell the value may
+    /// have changed.
+    version: u64,
 }
 
 /// Provide a context value for descendants.
 ///
 /// Call this inside a "Provider component" before rendering children.
+#[track_caller]
 pub fn provide_context<T>(ctx: Context<T>, value: T)
 where
     T: 'static + Clone,
 {
-    let fiber_state = read_fiber_state("`provide_context` was called outside of a fiber");
-
-    let idx = fiber_state.hook_index;
-    fiber_state.hook_index += 1;
-
-    if idx >= fiber_state.hooks.len() {
-        fiber_state.hooks.push(Hook {
-            type_id: TypeId::of::<ProvidedContext<T>>(),
-            state: Box::new(ProvidedContext {
-                ctx_id: ctx.id,
-                value,
-            }),
-        });
-        return;
-    }
+    provide_context_impl(ctx, value, None);
+}
 
-    let hook = &mut fiber_state.hooks[idx];
-    if hook.type_id != TypeId::of::<ProvidedContext<T>>() {
-        panic!(
-            "Expected `provide_context` hook, but got `{:?}`.",
-            hook.type_id
-        );
-    }
+/// Like [`provide_context`], but providing a value equal to the previous one
+/// isn't a change, e.g. for the [`Profiler`](crate::Profiler).
+#[track_caller]
+pub fn provide_context_eq<T>(ctx: Context<T>, value: T)
+where
+    T: 'static + Clone + PartialEq,
+{
+    provide_context_impl(ctx, value, Some(T::eq));
+}
 
-    let provided = hook
-        .state
-        .downcast_mut::<ProvidedContext<T>>()
-        .expect("type checked above");
+/// Like [`provide_context`], but [`inspect`](crate::inspect) shows the
+/// provided value, formatted with `Debug`.
+#[track_caller]
+pub fn provide_context_debug<T>(ctx: Context<T>, value: T)
+where
+    T: 'static + Clone + Debug,
+{
+    provide_context_impl(ctx, value, None).set_debug(|state| {
+        let provided = state
+            .downcast_ref::<ProvidedContext<T>>()
+            .expect("registered for this context type");
+        format!("{:?}", provided.value)
+    });
+}
 
-    if provided.ctx_id != ctx.id {
+#[track_caller]
+fn provide_context_impl<T>(
+    ctx: Context<T>,
+    value: T,
+    eq: Option<fn(&T, &T) -> bool>,
+) -> SlotRef<ProvidedContext<T>>
+where
+    T: 'static + Clone,
+{
+    let mut mount_value = Some(value);
+    let mut slot = hook_slot("provide_context", type_name::<T>(), || ProvidedContext {
+        ctx_id: ctx.id,
+        value: mount_value.take().unwrap(),
+        version: NEXT_PROVIDER_VERSION.fetch_add(1, Ordering::Relaxed),
+    });
+
+    if slot.ctx_id != ctx.id {
         panic!("Context mismatch: `provide_context` call order changed.");
     }
 
-    provided.value = value;
+    if let Some(value) = mount_value
+        && !eq.is_some_and(|eq| eq(&value, &slot.value))
+    {
+        slot.value = value;
+        slot.version = NEXT_PROVIDER_VERSION.fetch_add(1, Ordering::Relaxed);
+    }
+    slot
 }
 
 /// Read the nearest provided context value by walking up the active fiber stack.
@@ -95,7 +125,7 @@ pub fn use_context<T>(ctx: Context<T>) -> T
 where
     T: 'static + Clone,
 {
-    let location = caller_location();
+    let location = Location::caller();
 
     let mut current_id = CURRENT_FIBER_ID.with(|cell| cell.borrow().clone());
     if current_id.is_none() {
@@ -129,12 +159,14 @@ where
                         .state
                         .downcast_ref::<ProvidedContext<T>>()
                         .expect("type checked above");
-                    (provided.ctx_id == ctx.id).then(|| provided.value.clone())
+                    (provided.ctx_id == ctx.id).then(|| (provided.value.clone(), provided.version))
                 })
         };
 
-        if let Some(v) = found {
-            return v;
+        if let Some((value, version)) = found {
+            let reader = read_fiber_state("`use_context` was called outside of a fiber");
+            reader.context_reads.push((ctx.id, version));
+            return value;
         }
 
         current_id = get_parent_id(id).ok().flatten();
@@ -1,46 +1,103 @@
-use std::{any::TypeId, intrinsics::caller_location};
+use std::any::{Any, type_name};
 
-use crate::{
-    hooks::{Hook, read_fiber_state},
-    utils::{DynEq, deps_changed},
-};
+use crate::{fiber::is_strict_mode, hooks::slot::hook_slot, utils::Deps};
 
 pub(crate) struct UseEffect {
-    deps: Vec<Box<dyn DynEq>>,
+    deps: Box<dyn Any>,
+    cleanup: Option<Box<dyn FnOnce()>>,
 }
 
+impl UseEffect {
+    pub(crate) fn run_cleanup(&mut self) {
+        if let Some(cleanup) = self.cleanup.take() {
+            cleanup();
+        }
+    }
+}
+
+/// Value returned by an effect.
+///
+/// `()` means the effect has nothing to clean up. A closure is stored and run
+/// before the effect runs again, and when the fiber is unmounted.
+pub trait EffectCleanup: 'static {
+    fn into_cleanup(self) -> Option<Box<dyn FnOnce()>>;
+}
+
+impl EffectCleanup for () {
+    fn into_cleanup(self) -> Option<Box<dyn FnOnce()>> {
+        None
+    }
+}
+
+impl<F: FnOnce() + 'static> EffectCleanup for F {
+    fn into_cleanup(self) -> Option<Box<dyn FnOnce()>> {
+        Some(Box::new(self))
+    }
+}
+
+/// Runs `effect` on mount and whenever `deps` change between renders.
+///
+/// `deps` can be a tuple of values, `()` to only run on mount,
+/// [`EveryRender`](crate::EveryRender) to run after every render, or a boxed
+/// list built with [`deps!`](crate::deps). See [`Deps`].
+///
+/// The effect may return a cleanup closure, which runs before the effect runs
+/// again and when the fiber is unmounted.
 #[track_caller]
-pub fn use_effect(effect: &mut impl FnMut(), deps: Vec<Box<dyn DynEq>>) {
-    let location = caller_location();
-
-    let fiber_state = read_fiber_state(&format!(
-        "Hook `use_effect` was called outside of a fiber. ({})",
-        location
-    ));
-
-    let idx = fiber_state.hook_index;
-    fiber_state.hook_index += 1;
-
-    if idx >= fiber_state.hooks.len() {
-        // MOUNT LOGIC HERE
-        fiber_state.hooks.push(Hook {
-            type_id: TypeId::of::<UseEffect>(),
-            state: Box::new(UseEffect { deps }),
-        });
-        effect();
+pub fn use_effect<C, D>(effect: &mut impl FnMut() -> C, deps: D)
+where
+    C: EffectCleanup,
+    D: Deps,
+{
+    let mut mount_deps = Some(deps);
+    let mut slot = hook_slot("use_effect", type_name::<D>(), || UseEffect {
+        deps: Box::new(mount_deps.take().unwrap()),
+        cleanup: None,
+    });
+
+    let fiber_ptr = slot.fiber_ptr();
+    let mut effect = || {
+        unsafe { (*fiber_ptr).effect_count += 1 };
+        effect().into_cleanup()
+    };
+
+    if slot.is_mount() {
+        #[cfg(feature = "tracing")]
+        tracing::debug!(index = slot.index(), "effect mount");
+
+        slot.cleanup = effect();
+
+        // Strict mode simulates an immediate unmount + remount of the effect.
+        if is_strict_mode() {
+            slot.run_cleanup();
+            slot.cleanup = effect();
+        }
+
+        slot.on_unmount(UseEffect::run_cleanup);
         return;
     }
 
-    // UPDATE LOGIC HERE
-    let hook = &mut fiber_state.hooks[idx];
-    if hook.type_id != TypeId::of::<UseEffect>() {
-        panic!("Expected `use_effect` hook, but got `{:?}`.", hook.type_id);
-    }
-    let use_effect = hook.state.downcast_mut::<UseEffect>().unwrap();
-    let prev_deps = &use_effect.deps;
+    let deps = mount_deps.unwrap();
+    #[cfg(feature = "tracing")]
+    let index = slot.index();
+    let use_effect = &mut *slot;
+
+    // Dependencies of a different type than last render always count as changed.
+    let changed = use_effect
+        .deps
+        .downcast_ref::<D>()
+        .is_none_or(|prev_deps| deps.changed(prev_deps));
+
+    if changed {
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            index,
+            changed_dep = ?use_effect.deps.downcast_ref::<D>().and_then(|prev| deps.changed_dep(prev)),
+            "effect update"
+        );
 
-    if deps_changed(prev_deps, &deps) {
-        effect();
-        use_effect.deps = deps;
+        use_effect.run_cleanup();
+        use_effect.cleanup = effect();
+        use_effect.deps = Box::new(deps);
     }
 }
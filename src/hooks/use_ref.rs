@@ -1,6 +1,6 @@
-use std::{any::TypeId, cell::RefCell, intrinsics::caller_location, rc::Rc};
+use std::{any::type_name, cell::RefCell, fmt::Debug, rc::Rc};
 
-use crate::hooks::{Hook, read_fiber_state};
+use crate::hooks::slot::{SlotRef, debug_cell, hook_slot};
 
 pub(crate) struct UseRef<S> {
     current: Rc<RefCell<S>>,
@@ -8,34 +8,21 @@ pub(crate) struct UseRef<S> {
 
 #[track_caller]
 pub fn use_ref<S: 'static>(initial_value: S) -> Rc<RefCell<S>> {
-    let location = caller_location();
-
-    let fiber_state = read_fiber_state(&format!(
-        "Hook `use_ref` was called outside of a fiber. ({})",
-        location
-    ));
-
-    let idx = fiber_state.hook_index;
-    fiber_state.hook_index += 1;
-
-    if idx >= fiber_state.hooks.len() {
-        // MOUNT LOGIC HERE
-        let rc = Rc::new(RefCell::new(initial_value));
-        fiber_state.hooks.push(Hook {
-            type_id: TypeId::of::<UseRef<S>>(),
-            state: Box::new(UseRef {
-                current: rc.clone(),
-            }),
-        });
-        return rc;
-    }
+    use_ref_slot(initial_value).current.clone()
+}
 
-    // UPDATE LOGIC HERE
-    let hook = &fiber_state.hooks[idx];
-    if hook.type_id != TypeId::of::<UseRef<S>>() {
-        panic!("Expected `use_ref` hook, but got `{:?}`.", hook.type_id);
-    }
+/// Like [`use_ref`], but [`inspect`](crate::inspect) shows the current value,
+/// formatted with `Debug`.
+#[track_caller]
+pub fn use_ref_debug<S: Debug + 'static>(initial_value: S) -> Rc<RefCell<S>> {
+    let mut slot = use_ref_slot(initial_value);
+    slot.set_debug(|state| debug_cell(&state.downcast_ref::<UseRef<S>>().unwrap().current));
+    slot.current.clone()
+}
 
-    let use_ref = hook.state.downcast_ref::<UseRef<S>>().unwrap();
-    use_ref.current.clone()
+#[track_caller]
+fn use_ref_slot<S: 'static>(initial_value: S) -> SlotRef<UseRef<S>> {
+    hook_slot("use_ref", type_name::<S>(), || UseRef {
+        current: Rc::new(RefCell::new(initial_value)),
+    })
 }
@@ -1,12 +1,50 @@
-use std::{any::TypeId, intrinsics::caller_location};
+use std::any::{Any, TypeId, type_name};
 
 use crate::{
-    fiber::HooksState,
-    hooks::{Hook, read_fiber_state},
+    fiber::{FIBER_TREE, FiberNode, HooksState, RemoteSender, notify, schedule_update},
+    history,
+    hooks::slot::hook_slot,
 };
 
+type Update<S> = Box<dyn FnOnce(&S) -> S>;
+
 pub(crate) struct UseState<S> {
     value: S,
+    /// Updates waiting to be processed at the start of the next render.
+    queue: Vec<Update<S>>,
+    /// Value computed by immediate or compared updates that haven't been applied yet.
+    pending: Option<S>,
+    immediate: bool,
+    /// Set by [`use_state_eq`] to skip updates that produce an equal value.
+    eq: Option<fn(&S, &S) -> bool>,
+}
+
+impl<S: Clone + 'static> UseState<S> {
+    /// Replaces the value, recording the change if the [`History`](crate::History) recorder is on.
+    fn set_value(&mut self, value: S, setter: &SetStateAction<S>) {
+        history::record(
+            setter.fiber_ptr,
+            setter.hook_index,
+            &self.value,
+            &value,
+            restore_state::<S>,
+        );
+        self.value = value;
+    }
+}
+
+/// Writes a value recorded by the [`History`](crate::History) back into a `use_state` hook.
+fn restore_state<S: Clone + 'static>(state: &mut dyn Any, value: &dyn Any) -> bool {
+    let (Some(use_state), Some(value)) = (
+        state.downcast_mut::<UseState<S>>(),
+        value.downcast_ref::<S>(),
+    ) else {
+        return false;
+    };
+    use_state.value = value.clone();
+    use_state.pending = None;
+    use_state.queue.clear();
+    true
 }
 
 /// Declares a stateful value that persists across renders.
@@ -36,83 +74,388 @@ pub(crate) struct UseState<S> {
 ///
 /// - **Mount phase**: initializes state using `initial` and stores it
 ///   in the fiber's hook list.
-/// - **Update phase**: retrieves the previously stored state for the
-///   current hook index.
+/// - **Update phase**: processes the queued updates in order, then returns
+///   the resulting state for the current hook index.
 ///
 /// The returned setter is stable across renders and may be cloned and
-/// called multiple times. Calling the setter replaces the stored state
-/// with a new value derived from the previous one.
+/// called multiple times. Calling the setter appends an update to the hook's
+/// queue and marks the fiber dirty; the state itself only changes at the start
+/// of the fiber's next render, so a render always sees a single value.
+/// Updates made during a render or inside [`batch`](crate::batch) are queued
+/// once the render or the batch has finished.
+///
+/// See [`use_state_immediate`] for a setter that applies updates right away.
 ///
 /// # Examples
 ///
 /// ```rust
-/// let (count, set_count) = use_state(|| 0);
+/// use hooks_rs::{call_fiber, mount_fiber, use_state};
+///
+/// fn counter(_: ()) -> i32 {
+///     let (count, set_count) = use_state(|| 0);
+///     set_count.update(|prev| prev + 1);
+///     count
+/// }
+///
+/// mount_fiber(None, "counter", counter).unwrap();
 ///
-/// set_count(|prev| prev + 1);
+/// assert_eq!(call_fiber::<(), i32>("counter", ()).unwrap(), 0);
+/// assert_eq!(call_fiber::<(), i32>("counter", ()).unwrap(), 1);
 /// ```
 #[track_caller]
 pub fn use_state<S>(initial: impl FnOnce() -> S) -> (S, SetStateAction<S>)
 where
     S: 'static + Clone,
 {
-    let location = caller_location();
+    use_state_impl(initial, false, None)
+}
 
-    let fiber_state = read_fiber_state(&format!(
-        "Hook `use_state` was called outside of a fiber. ({})",
-        location
-    ));
+/// Like [`use_state`], but updates that produce a value equal to the current
+/// one are dropped and leave the fiber clean.
+///
+/// Updates are evaluated as soon as they are queued so they can be compared,
+/// and the result is applied at the start of the next render.
+#[track_caller]
+pub fn use_state_eq<S>(initial: impl FnOnce() -> S) -> (S, SetStateAction<S>)
+where
+    S: 'static + Clone + PartialEq,
+{
+    use_state_impl(initial, false, Some(S::eq))
+}
 
-    let idx = fiber_state.hook_index;
-    fiber_state.hook_index += 1;
+/// Like [`use_state`], but the setter applies each update right away instead of
+/// queueing it for the next render.
+///
+/// Updates made during a render or inside [`batch`](crate::batch) are still
+/// applied once the render or the batch has finished.
+#[track_caller]
+pub fn use_state_immediate<S>(initial: impl FnOnce() -> S) -> (S, SetStateAction<S>)
+where
+    S: 'static + Clone,
+{
+    use_state_impl(initial, true, None)
+}
 
-    if idx >= fiber_state.hooks.len() {
-        // MOUNT LOGIC HERE
-        fiber_state.hooks.push(Hook {
-            type_id: TypeId::of::<UseState<S>>(),
-            state: Box::new(UseState { value: initial() }),
-        });
-    }
+/// Like [`use_state`], but [`inspect`](crate::inspect) shows the state's value,
+/// formatted with `Debug`.
+#[track_caller]
+pub fn use_state_debug<S>(initial: impl FnOnce() -> S) -> (S, SetStateAction<S>)
+where
+    S: 'static + Clone + std::fmt::Debug,
+{
+    let (state, setter) = use_state_impl(initial, false, None);
 
-    // UPDATE LOGIC HERE
-    let hook = &fiber_state.hooks[idx];
-    if hook.type_id != TypeId::of::<UseState<S>>() {
-        panic!("Expected `use_state` hook, but got `{:?}`.", hook.type_id);
-    }
-    let use_state = hook.state.downcast_ref::<UseState<S>>().unwrap();
-    let state = use_state.value.clone();
+    let fiber = unsafe { &mut *setter.fiber_ptr };
+    fiber.hooks[setter.hook_index].debug = Some(|state| {
+        let use_state = state
+            .downcast_ref::<UseState<S>>()
+            .expect("registered for this state type");
+        format!("{:?}", use_state.value)
+    });
+
+    (state, setter)
+}
+
+/// Like [`use_state`], but the state is saved by [`snapshot`](crate::snapshot)
+/// and can be hydrated with [`restore`](crate::restore).
+///
+/// On mount, a restored value takes the place of `initial`.
+#[cfg(feature = "serde")]
+#[track_caller]
+pub fn use_state_serde<S>(initial: impl FnOnce() -> S) -> (S, SetStateAction<S>)
+where
+    S: 'static + Clone + serde::Serialize + serde::de::DeserializeOwned,
+{
+    let (state, setter) = use_state_impl(
+        || crate::snapshot::take_hydrated("use_state").unwrap_or_else(initial),
+        false,
+        None,
+    );
+
+    let fiber = unsafe { &mut *setter.fiber_ptr };
+    let hook = &mut fiber.hooks[setter.hook_index];
+    hook.serialize = Some(serialize_state::<S>);
+    hook.debug =
+        Some(|state| serialize_state::<S>(state).map_or_else(|err| err, |v| v.to_string()));
+
+    (state, setter)
+}
+
+#[cfg(feature = "serde")]
+fn serialize_state<S: serde::Serialize + 'static>(
+    state: &dyn std::any::Any,
+) -> Result<serde_json::Value, String> {
+    let use_state = state
+        .downcast_ref::<UseState<S>>()
+        .expect("registered for this state type");
+    serde_json::to_value(&use_state.value).map_err(|err| err.to_string())
+}
+
+#[track_caller]
+fn use_state_impl<S>(
+    initial: impl FnOnce() -> S,
+    immediate: bool,
+    eq: Option<fn(&S, &S) -> bool>,
+) -> (S, SetStateAction<S>)
+where
+    S: 'static + Clone,
+{
+    let mut slot = hook_slot("use_state", type_name::<S>(), || UseState {
+        value: initial(),
+        queue: Vec::new(),
+        pending: None,
+        immediate,
+        eq,
+    });
 
     let setter = SetStateAction::<S> {
-        fiber_ptr: &mut *fiber_state,
-        hook_index: idx,
+        fiber_ptr: slot.fiber_ptr(),
+        uid: slot.uid(),
+        hook_index: slot.index(),
         _marker: std::marker::PhantomData,
     };
 
+    let use_state = &mut *slot;
+    if !use_state.immediate
+        && let Some(value) = use_state.pending.take()
+    {
+        use_state.set_value(value, &setter);
+    }
+    for update in std::mem::take(&mut use_state.queue) {
+        let value = update(&use_state.value);
+        use_state.set_value(value, &setter);
+    }
+    let state = use_state.value.clone();
+
     (state, setter)
 }
 
-/// --------------------------- React.Dispatch<SetStateAction<T>> from wish
+// --------------------------- React.Dispatch<SetStateAction<T>> from wish
 
+/// Setter returned by [`use_state`].
+///
+/// Call [`set`](Self::set) to replace the state or [`update`](Self::update) to
+/// derive it from the previous value. With the `nightly` feature the setter can
+/// also be called like a function: `set_count(|prev| prev + 1)`.
+///
+/// Setters point into the fiber tree of the thread that rendered them, so
+/// they can't be sent to other threads:
+///
+/// ```rust,compile_fail
+/// use hooks_rs::{call_fiber, mount_fiber, use_state};
+///
+/// fn component(_: ()) {
+///     let (_, set_count) = use_state(|| 0);
+///     std::thread::spawn(move || set_count.set(1));
+/// }
+/// ```
 pub struct SetStateAction<S> {
     fiber_ptr: *mut HooksState,
+    /// Uid of the fiber state, checked before `fiber_ptr` is dereferenced.
+    uid: u64,
     hook_index: usize,
     _marker: std::marker::PhantomData<S>,
 }
 
 impl<S: Clone + 'static> SetStateAction<S> {
-    fn set(&self, f: &dyn Fn(&S) -> S) {
-        unsafe {
-            let fiber = &mut *self.fiber_ptr;
-            let hook = &mut fiber.hooks[self.hook_index];
-            if hook.type_id != TypeId::of::<UseState<S>>() {
-                panic!("Expected `use_state` hook, but got `{:?}`.", hook.type_id);
+    /// Replaces the state with `value`.
+    pub fn set(&self, value: S) {
+        self.update(move |_| value.clone());
+    }
+
+    /// Replaces the state with a value derived from the previous one.
+    ///
+    /// Does nothing once the fiber is unmounted.
+    pub fn update(&self, f: impl Fn(&S) -> S + 'static) {
+        if !HooksState::is_live(self.uid) {
+            return;
+        }
+        let setter = *self;
+        let use_state = self.use_state();
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            fiber = %unsafe { &(*self.fiber_ptr).fiber_id },
+            index = self.hook_index,
+            state = std::any::type_name::<S>(),
+            "set state"
+        );
+
+        if use_state.immediate {
+            let prev = use_state.pending.as_ref().unwrap_or(&use_state.value);
+            use_state.pending = Some(f(prev));
+            schedule_update(self.uid, Box::new(move || setter.apply()));
+        } else {
+            schedule_update(self.uid, Box::new(move || setter.enqueue(Box::new(f))));
+        }
+    }
+
+    /// Appends an update to the hook's queue.
+    ///
+    /// Hooks with an equality check evaluate the update right away instead,
+    /// and drop it if it doesn't change the value.
+    fn enqueue(&self, update: Update<S>) {
+        let use_state = self.use_state();
+
+        if let Some(eq) = use_state.eq {
+            let prev = use_state.pending.as_ref().unwrap_or(&use_state.value);
+            let next = update(prev);
+            if eq(&next, prev) {
+                return;
             }
-            let use_state = hook.state.downcast_mut::<UseState<S>>().unwrap();
-            use_state.value = f(&use_state.value);
+            use_state.pending = Some(next);
+        } else {
+            use_state.queue.push(update);
+        }
+
+        self.mark_dirty();
+    }
+
+    /// Applies the pending value of an immediate update, if it hasn't been applied already.
+    fn apply(&self) {
+        let use_state = self.use_state();
+        if let Some(value) = use_state.pending.take() {
+            use_state.set_value(value, self);
+            self.mark_dirty();
         }
     }
+
+    /// The setter of the `use_state` hook at `hook_index` in the mount `uid`
+    /// of a fiber, if it is still mounted and the hook holds an `S`.
+    ///
+    /// The fiber is looked up by id first, then by uid in case it moved.
+    fn find(fiber_id: &str, uid: u64, hook_index: usize) -> Option<Self> {
+        let fiber_ptr = FIBER_TREE.with(|t| {
+            let tree = t.borrow();
+            let state_ptr = |node: &FiberNode| node.fiber.borrow().state_ptr() as *mut HooksState;
+            let is_mount = |ptr: *mut HooksState| unsafe { (*ptr).uid == uid };
+
+            tree.0
+                .get(fiber_id)
+                .map(state_ptr)
+                .filter(|&ptr| is_mount(ptr))
+                .or_else(|| tree.0.values().map(state_ptr).find(|&ptr| is_mount(ptr)))
+        })?;
+
+        let fiber = unsafe { &*fiber_ptr };
+        let hook = fiber.hooks.get(hook_index)?;
+        (hook.type_id == TypeId::of::<UseState<S>>()).then_some(SetStateAction {
+            fiber_ptr,
+            uid,
+            hook_index,
+            _marker: std::marker::PhantomData,
+        })
+    }
+
+    fn mark_dirty(&self) {
+        let fiber = unsafe { &mut *self.fiber_ptr };
+        fiber.mark_dirty(self.hook_index);
+        notify(|observer| observer.on_state_change(&fiber.fiber_id, self.hook_index));
+    }
+
+    fn use_state(&self) -> &'static mut UseState<S> {
+        let fiber = unsafe { &mut *self.fiber_ptr };
+        let hook = &mut fiber.hooks[self.hook_index];
+        if hook.type_id != TypeId::of::<UseState<S>>() {
+            panic!(
+                "Expected `use_state` hook at slot {}, but got `{}`.",
+                self.hook_index, hook.kind
+            );
+        }
+        hook.state.downcast_mut::<UseState<S>>().unwrap()
+    }
+}
+
+impl<S: Clone + Send + 'static> SetStateAction<S> {
+    /// A handle to this setter that can be sent to other threads.
+    ///
+    /// Its updates are queued for the thread that rendered the hook, and
+    /// applied there by [`process_remote_updates`](crate::process_remote_updates).
+    ///
+    /// ```rust
+    /// use hooks_rs::{SetStateAction, call_fiber, mount_fiber, process_remote_updates, use_state};
+    ///
+    /// type Counter = (i32, SetStateAction<i32>);
+    ///
+    /// fn component(_: ()) -> Counter {
+    ///     use_state(|| 0)
+    /// }
+    ///
+    /// mount_fiber(None, "root", component).unwrap();
+    /// let (_, set_count) = call_fiber::<(), Counter>("root", ()).unwrap();
+    ///
+    /// let remote = set_count.remote();
+    /// std::thread::spawn(move || remote.set(5)).join().unwrap();
+    ///
+    /// process_remote_updates();
+    /// assert_eq!(call_fiber::<(), Counter>("root", ()).unwrap().0, 5);
+    /// ```
+    pub fn remote(&self) -> RemoteSetter<S> {
+        // The updates of an unmounted fiber are dropped, whatever its id.
+        let fiber_id = if HooksState::is_live(self.uid) {
+            unsafe { (*self.fiber_ptr).fiber_id.clone() }
+        } else {
+            String::new()
+        };
+        RemoteSetter {
+            sender: RemoteSender::current(),
+            fiber_id,
+            uid: self.uid,
+            hook_index: self.hook_index,
+            _marker: std::marker::PhantomData,
+        }
+    }
+}
+
+/// A [`SetStateAction`] that can be sent to other threads, see
+/// [`SetStateAction::remote`].
+pub struct RemoteSetter<S> {
+    sender: RemoteSender,
+    fiber_id: String,
+    /// Mount of the fiber the setter belongs to, so a fiber mounted later with
+    /// the same id doesn't receive its updates.
+    uid: u64,
+    hook_index: usize,
+    _marker: std::marker::PhantomData<fn(S)>,
+}
+
+impl<S: Clone + Send + 'static> RemoteSetter<S> {
+    /// Replaces the state with `value`.
+    pub fn set(&self, value: S) {
+        self.update(move |_| value.clone());
+    }
+
+    /// Replaces the state with a value derived from the previous one.
+    pub fn update(&self, f: impl Fn(&S) -> S + Send + 'static) {
+        let fiber_id = self.fiber_id.clone();
+        let (uid, hook_index) = (self.uid, self.hook_index);
+        self.sender.send(Box::new(move || {
+            if let Some(setter) = SetStateAction::<S>::find(&fiber_id, uid, hook_index) {
+                setter.update(f);
+            }
+        }));
+    }
+
+    /// Whether updates sent to the render thread haven't been processed yet,
+    /// by any setter.
+    pub fn has_pending_updates(&self) -> bool {
+        self.sender.has_pending()
+    }
 }
 
-/// --------------------------- Clone / Copy to mimick react like usage
+impl<S> Clone for RemoteSetter<S> {
+    fn clone(&self) -> Self {
+        Self {
+            sender: self.sender.clone(),
+            fiber_id: self.fiber_id.clone(),
+            uid: self.uid,
+            hook_index: self.hook_index,
+            _marker: std::marker::PhantomData,
+        }
+    }
+}
+
+// --------------------------- Clone / Copy to mimick react like usage
 // Manually implement these traits since deriving them also makes S: Clone / S: Copy
 impl<S> Clone for SetStateAction<S> {
     fn clone(&self) -> Self {
@@ -121,36 +464,36 @@ impl<S> Clone for SetStateAction<S> {
 }
 impl<S> Copy for SetStateAction<S> {}
 
-unsafe impl<S> Send for SetStateAction<S> {}
-unsafe impl<S> Sync for SetStateAction<S> {}
-
 // --------------------------- Fn Traits so SetStateAction can be used like a closure
+#[cfg(feature = "nightly")]
 impl<S, F> FnOnce<(F,)> for SetStateAction<S>
 where
     S: Clone + 'static,
-    F: Fn(&'_ S) -> S,
+    F: Fn(&'_ S) -> S + 'static,
 {
     type Output = ();
 
     extern "rust-call" fn call_once(self, args: (F,)) -> Self::Output {
-        self.set(&args.0)
+        self.update(args.0)
     }
 }
 
+#[cfg(feature = "nightly")]
 impl<S: Clone + 'static, F> FnMut<(F,)> for SetStateAction<S>
 where
-    F: Fn(&'_ S) -> S,
+    F: Fn(&'_ S) -> S + 'static,
 {
     extern "rust-call" fn call_mut(&mut self, args: (F,)) -> Self::Output {
-        self.set(&args.0)
+        self.update(args.0)
     }
 }
 
+#[cfg(feature = "nightly")]
 impl<S: Clone + 'static, F> Fn<(F,)> for SetStateAction<S>
 where
-    F: Fn(&'_ S) -> S,
+    F: Fn(&'_ S) -> S + 'static,
 {
     extern "rust-call" fn call(&self, args: (F,)) -> Self::Output {
-        self.set(&args.0)
+        self.update(args.0)
     }
 }
@@ -0,0 +1,147 @@
+use std::{any::Any, fmt::Display};
+
+use crate::fiber::{FIBER_TREE, FiberTree};
+
+/// Formats a type-erased hook state, registered by the hooks that support it.
+pub(crate) type DebugFn = fn(&dyn Any) -> String;
+
+/// A snapshot of the fiber tree for debugging, created by [`inspect`].
+///
+/// `Display` prints it as an indented text tree.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Inspection {
+    /// Fibers without a parent, sorted by id.
+    pub roots: Vec<InspectedFiber>,
+}
+
+/// A fiber and its descendants, as seen by [`inspect`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InspectedFiber {
+    pub id: String,
+    pub parent: Option<String>,
+    /// Number of times the fiber rendered.
+    pub render_count: u64,
+    pub hooks: Vec<InspectedHook>,
+    /// Children in mount order.
+    pub children: Vec<InspectedFiber>,
+}
+
+/// A hook slot, as seen by [`inspect`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InspectedHook {
+    pub index: usize,
+    /// Hook kind, e.g. `use_state`.
+    pub kind: String,
+    /// Type of the value held by the hook.
+    pub type_name: String,
+    /// `Debug` representation of the value, for hooks that provide one, e.g.
+    /// [`use_state_debug`](crate::use_state_debug) or
+    /// [`HookSlotRef::debug_value`](crate::HookSlotRef::debug_value).
+    pub value: Option<String>,
+}
+
+/// Returns the current fiber tree with the hooks of every fiber.
+///
+/// ```rust
+/// use hooks_rs::{call_fiber, inspect, mount_fiber, use_state};
+///
+/// fn counter(_: ()) -> i32 {
+///     let (count, _) = use_state(|| 0);
+///     count
+/// }
+///
+/// mount_fiber(None, "counter", counter).unwrap();
+/// call_fiber::<(), i32>("counter", ()).unwrap();
+///
+/// let inspection = inspect();
+/// assert_eq!(inspection.roots[0].render_count, 1);
+/// assert_eq!(inspection.roots[0].hooks[0].type_name, "i32");
+///
+/// println!("{inspection}");
+/// ```
+pub fn inspect() -> Inspection {
+    FIBER_TREE.with(|t| {
+        let tree = t.borrow();
+
+        let mut roots: Vec<&String> = tree
+            .0
+            .iter()
+            .filter(|(_, node)| node.parent.is_none())
+            .map(|(id, _)| id)
+            .collect();
+        roots.sort();
+
+        Inspection {
+            roots: roots
+                .into_iter()
+                .map(|id| inspect_fiber(&tree, id))
+                .collect(),
+        }
+    })
+}
+
+fn inspect_fiber(tree: &FiberTree, id: &str) -> InspectedFiber {
+    let node = &tree.0[id];
+    let state_ptr = node.fiber.borrow().state_ptr();
+    let state = unsafe { &*state_ptr };
+
+    let hooks = state
+        .hooks
+        .iter()
+        .enumerate()
+        .map(|(index, hook)| InspectedHook {
+            index,
+            kind: hook.kind.to_string(),
+            type_name: hook.type_name.to_string(),
+            value: hook.debug.map(|debug| debug(&*hook.state)),
+        })
+        .collect();
+
+    InspectedFiber {
+        id: id.to_string(),
+        parent: node.parent.clone(),
+        render_count: state.render_count,
+        hooks,
+        children: node
+            .children
+            .iter()
+            .map(|child| inspect_fiber(tree, child))
+            .collect(),
+    }
+}
+
+impl Display for Inspection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for root in &self.roots {
+            root.write_indented(f, 0)?;
+        }
+        Ok(())
+    }
+}
+
+impl InspectedFiber {
+    fn write_indented(&self, f: &mut std::fmt::Formatter<'_>, depth: usize) -> std::fmt::Result {
+        let indent = "  ".repeat(depth);
+        writeln!(f, "{indent}{} (renders: {})", self.id, self.render_count)?;
+
+        for hook in &self.hooks {
+            write!(
+                f,
+                "{indent}  [{}] {}<{}>",
+                hook.index, hook.kind, hook.type_name
+            )?;
+            match &hook.value {
+                Some(value) => writeln!(f, " = {value}")?,
+                None => writeln!(f)?,
+            }
+        }
+
+        for child in &self.children {
+            child.write_indented(f, depth + 1)?;
+        }
+        Ok(())
+    }
+}
@@ -1,8 +1,17 @@
-#![feature(unboxed_closures, fn_traits, core_intrinsics)]
+#![cfg_attr(feature = "nightly", feature(unboxed_closures, fn_traits))]
 // modules
 mod error;
 mod fiber;
+mod history;
 mod hooks;
+mod inspect;
+mod profiler;
+mod reconciler;
+#[cfg(feature = "serde")]
+mod snapshot;
+#[cfg(feature = "sync")]
+pub mod sync;
+pub mod testing;
 mod utils;
 
 // ------------------------------------ API surface ------------------------------------
@@ -11,17 +20,48 @@ mod utils;
 pub use error::FiberStoreError;
 
 // ----------------- Fiber Management
-pub use fiber::{call_fiber, get_children_ids, get_parent_id, mount_fiber, unmount_fiber};
+pub use fiber::{
+    ChildChanges, FiberObserver, ObserverId, add_observer, batch, call_fiber, get_children_ids,
+    get_dirty_ids, get_parent_id, is_fiber_dirty, mount_fiber, mount_fiber_at, move_fiber,
+    process_remote_updates, reconcile_children, remove_observer, replace_fiber_fn, unmount_fiber,
+    was_fiber_reset,
+};
+
+// ----------------- Components
+pub use fiber::{Component, FiberHandle, mount_component};
+pub use hooks_rs_macros::component;
+
+// ----------------- Rendering
+pub use reconciler::{
+    ComponentNode, RenderedChild, RenderedRoot, Renderer, Root, VNode, create_root,
+};
 
 // ----------------- Hooks
 
 // --- Hook Creation
-pub use hooks::{Hook, read_fiber_state};
 pub use fiber::HooksState;
+pub use hooks::slot::{HookSlotRef, use_hook_slot};
+pub use hooks::{Hook, read_fiber_state};
 
 // --- Default hooks
-pub use hooks::use_context::{Context, create_context, provide_context, use_context};
-pub use hooks::use_effect::use_effect;
-pub use utils::DynEq;
-pub use hooks::use_ref::use_ref;
-pub use hooks::use_state::{SetStateAction, use_state};
+pub use hooks::use_context::{
+    Context, create_context, provide_context, provide_context_debug, provide_context_eq,
+    use_context,
+};
+pub use hooks::use_effect::{EffectCleanup, use_effect};
+pub use hooks::use_ref::{use_ref, use_ref_debug};
+pub use hooks::use_state::{
+    RemoteSetter, SetStateAction, use_state, use_state_debug, use_state_eq, use_state_immediate,
+};
+pub use utils::{Deps, DynEq, EveryRender};
+
+// --- Snapshots
+#[cfg(feature = "serde")]
+pub use hooks::use_state::use_state_serde;
+#[cfg(feature = "serde")]
+pub use snapshot::{RestoreReport, SkippedSlot, Snapshot, restore, snapshot};
+
+// ----------------- Devtools
+pub use history::{History, HistoryEntry};
+pub use inspect::{InspectedFiber, InspectedHook, Inspection, inspect};
+pub use profiler::{FiberStats, Profiler, RenderReason, RenderRecord};
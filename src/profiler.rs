@@ -0,0 +1,239 @@
+use std::{
+    cell::RefCell,
+    collections::BTreeMap,
+    fmt::{Display, Write},
+    time::{Duration, Instant},
+};
+
+use crate::fiber::HooksState;
+
+thread_local! {
+    static PROFILER: RefCell<Option<Profiler>> = const { RefCell::new(None) };
+}
+
+/// Why a fiber rendered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum RenderReason {
+    /// First render of the fiber.
+    Mount,
+    /// The fiber was called directly, with no other reason to render.
+    ///
+    /// Props aren't required to be comparable, so any such call counts as new props.
+    Props,
+    /// An update to the state hook at `hook_index`.
+    State { hook_index: usize },
+    /// A provider of a context read by the fiber rendered since its last render.
+    Context,
+    /// The fiber was called while another fiber was rendering.
+    Parent,
+}
+
+impl Display for RenderReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RenderReason::Mount => write!(f, "mount"),
+            RenderReason::Props => write!(f, "props"),
+            RenderReason::State { hook_index } => write!(f, "state[{hook_index}]"),
+            RenderReason::Context => write!(f, "context"),
+            RenderReason::Parent => write!(f, "parent"),
+        }
+    }
+}
+
+/// A single `call_fiber`, recorded by the [`Profiler`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenderRecord {
+    pub fiber_id: String,
+    /// Time between [`Profiler::start`] and the start of the render.
+    pub start: Duration,
+    /// Duration of the render, including the fibers it called.
+    pub duration: Duration,
+    pub reasons: Vec<RenderReason>,
+}
+
+/// Aggregated [`RenderRecord`]s of a fiber.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FiberStats {
+    pub render_count: u64,
+    pub total_duration: Duration,
+    pub max_duration: Duration,
+    /// Number of renders for each reason. A render can have several reasons.
+    pub reasons: BTreeMap<RenderReason, u64>,
+}
+
+impl FiberStats {
+    /// Average duration of a render.
+    pub fn mean_duration(&self) -> Duration {
+        match self.render_count {
+            0 => Duration::ZERO,
+            n => self.total_duration / n as u32,
+        }
+    }
+}
+
+/// Records the duration and the reasons of every render on the current thread.
+///
+/// ```rust
+/// use hooks_rs::{Profiler, RenderReason, call_fiber, mount_fiber};
+///
+/// fn component(_: ()) {}
+///
+/// mount_fiber(None, "root", component).unwrap();
+///
+/// Profiler::start();
+/// call_fiber::<(), ()>("root", ()).unwrap();
+/// call_fiber::<(), ()>("root", ()).unwrap();
+/// let profiler = Profiler::stop().unwrap();
+///
+/// let stats = &profiler.stats()["root"];
+/// assert_eq!(stats.render_count, 2);
+/// assert_eq!(stats.reasons[&RenderReason::Mount], 1);
+///
+/// std::fs::write(std::env::temp_dir().join("trace.json"), profiler.to_chrome_trace()).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct Profiler {
+    started_at: Instant,
+    records: Vec<RenderRecord>,
+}
+
+impl Profiler {
+    /// Starts profiling the renders of the current thread, dropping any
+    /// profile in progress.
+    pub fn start() {
+        let profiler = Profiler {
+            started_at: Instant::now(),
+            records: Vec::new(),
+        };
+        PROFILER.with(|p| *p.borrow_mut() = Some(profiler));
+    }
+
+    /// Stops profiling and returns what was recorded since [`start`](Self::start).
+    pub fn stop() -> Option<Profiler> {
+        PROFILER.with(|p| p.borrow_mut().take())
+    }
+
+    /// Whether a profile is in progress on the current thread.
+    pub fn is_running() -> bool {
+        PROFILER.with(|p| p.borrow().is_some())
+    }
+
+    /// Renders in the order they finished.
+    pub fn records(&self) -> &[RenderRecord] {
+        &self.records
+    }
+
+    /// Render statistics, by fiber id.
+    pub fn stats(&self) -> BTreeMap<String, FiberStats> {
+        let mut stats = BTreeMap::<String, FiberStats>::new();
+        for record in &self.records {
+            let fiber = stats.entry(record.fiber_id.clone()).or_default();
+            fiber.render_count += 1;
+            fiber.total_duration += record.duration;
+            fiber.max_duration = fiber.max_duration.max(record.duration);
+            for reason in &record.reasons {
+                *fiber.reasons.entry(*reason).or_default() += 1;
+            }
+        }
+        stats
+    }
+
+    /// Exports the renders in the Chrome trace event format, which can be
+    /// opened in `chrome://tracing` or Perfetto.
+    pub fn to_chrome_trace(&self) -> String {
+        let mut json = String::from("{\"traceEvents\":[");
+        for (i, record) in self.records.iter().enumerate() {
+            if i > 0 {
+                json.push(',');
+            }
+            let reasons: Vec<String> = record
+                .reasons
+                .iter()
+                .map(|reason| json_string(&reason.to_string()))
+                .collect();
+            let _ = write!(
+                json,
+                "{{\"name\":{},\"cat\":\"render\",\"ph\":\"X\",\"ts\":{},\"dur\":{},\"pid\":1,\"tid\":1,\"args\":{{\"reasons\":[{}]}}}}",
+                json_string(&record.fiber_id),
+                record.start.as_secs_f64() * 1e6,
+                record.duration.as_secs_f64() * 1e6,
+                reasons.join(",")
+            );
+        }
+        json.push_str("],\"displayTimeUnit\":\"ms\"}");
+        json
+    }
+}
+
+fn json_string(value: &str) -> String {
+    let mut json = String::with_capacity(value.len() + 2);
+    json.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            c if c.is_control() => {
+                let _ = write!(json, "\\u{:04x}", c as u32);
+            }
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+/// A render in progress, started by [`begin_render`].
+pub(crate) struct RenderStart {
+    start: Instant,
+    reasons: Vec<RenderReason>,
+}
+
+/// Collects the reasons a fiber is about to render, if the profiler is running.
+pub(crate) fn begin_render(state: &HooksState, caller: Option<&String>) -> Option<RenderStart> {
+    if !Profiler::is_running() {
+        return None;
+    }
+
+    let mut reasons = Vec::new();
+    if state.render_count == 0 {
+        reasons.push(RenderReason::Mount);
+    }
+    reasons.extend(
+        state
+            .dirty_hooks
+            .iter()
+            .map(|&hook_index| RenderReason::State { hook_index }),
+    );
+    if caller.is_some() {
+        reasons.push(RenderReason::Parent);
+    }
+
+    Some(RenderStart {
+        start: Instant::now(),
+        reasons,
+    })
+}
+
+/// Records a finished render.
+pub(crate) fn end_render(render: RenderStart, state: &HooksState) {
+    let duration = render.start.elapsed();
+
+    let mut reasons = render.reasons;
+    if state.render_count > 1 && state.context_reads != state.prev_context_reads {
+        reasons.push(RenderReason::Context);
+    }
+    if reasons.is_empty() {
+        reasons.push(RenderReason::Props);
+    }
+
+    PROFILER.with(|p| {
+        if let Some(profiler) = p.borrow_mut().as_mut() {
+            profiler.records.push(RenderRecord {
+                fiber_id: state.fiber_id.clone(),
+                start: render.start.saturating_duration_since(profiler.started_at),
+                duration,
+                reasons,
+            });
+        }
+    });
+}
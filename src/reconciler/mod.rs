@@ -0,0 +1,284 @@
+mod renderer;
+pub use renderer::*;
+
+mod vnode;
+pub use vnode::*;
+
+use std::collections::{BTreeMap, HashSet};
+
+use crate::fiber::{call_fiber, mount_fiber, reconcile_children, unmount_fiber};
+
+/// The root of a [`VNode`] tree, created by [`create_root`].
+///
+/// Rendering the root renders its component, then every component it returned,
+/// mounting the fibers of new components and unmounting the fibers of
+/// components that are gone.
+///
+/// ```rust
+/// use hooks_rs::{RenderedChild, Renderer, VNode, create_root, use_state};
+///
+/// /// Renders elements as markup.
+/// struct Markup;
+///
+/// impl Renderer for Markup {
+///     type Element = &'static str;
+///     type Output = String;
+///
+///     fn element(&mut self, tag: &'static str, children: Vec<RenderedChild<String>>) -> String {
+///         let children: Vec<String> = children.into_iter().map(|c| c.output).collect();
+///         format!("<{tag}>{}</{tag}>", children.concat())
+///     }
+///
+///     fn fragment(&mut self, children: Vec<RenderedChild<String>>) -> String {
+///         children.into_iter().map(|c| c.output).collect()
+///     }
+/// }
+///
+/// fn counter(_: ()) -> VNode<&'static str> {
+///     let (count, set_count) = use_state(|| 0);
+///     set_count.update(|prev| prev + 1);
+///     VNode::element(if count == 0 { "zero" } else { "more" })
+/// }
+///
+/// fn app(_: ()) -> VNode<&'static str> {
+///     VNode::with_children("app", vec![VNode::component("counter", counter, ())])
+/// }
+///
+/// let mut root = create_root("app", app);
+/// assert_eq!(root.render(&mut Markup, ()), "<app><zero></zero></app>");
+/// assert_eq!(root.render(&mut Markup, ()), "<app><more></more></app>");
+/// ```
+pub struct Root<P, E> {
+    id: String,
+    /// The root component, until it is mounted.
+    fun: Option<Box<dyn FnMut(P) -> VNode<E>>>,
+}
+
+/// Creates a [`Root`] rendering `fun` in the fiber `id`.
+///
+/// The fiber is mounted on the first render.
+pub fn create_root<P, E>(
+    id: impl Into<String>,
+    fun: impl FnMut(P) -> VNode<E> + 'static,
+) -> Root<P, E>
+where
+    P: 'static,
+    E: 'static,
+{
+    Root {
+        id: id.into(),
+        fun: Some(Box::new(fun)),
+    }
+}
+
+impl<P, E> Root<P, E>
+where
+    P: 'static,
+    E: 'static,
+{
+    /// The id of the root fiber.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Renders the tree with `props`.
+    ///
+    /// Outputs rendered into [portals](VNode::portal) are dropped, use
+    /// [`render_with_portals`](Self::render_with_portals) to get them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the root fiber can't be mounted or called, e.g. because a
+    /// fiber with the same id was mounted by someone else.
+    pub fn render<R>(&mut self, renderer: &mut R, props: P) -> R::Output
+    where
+        R: Renderer<Element = E>,
+    {
+        self.render_with_portals(renderer, props).output
+    }
+
+    /// Renders the tree with `props`, along with the output of every portal
+    /// target.
+    ///
+    /// # Panics
+    ///
+    /// Panics like [`render`](Self::render).
+    pub fn render_with_portals<R>(&mut self, renderer: &mut R, props: P) -> RenderedRoot<R::Output>
+    where
+        R: Renderer<Element = E>,
+    {
+        if let Some(fun) = self.fun.take() {
+            mount_fiber(None, self.id.clone(), fun)
+                .unwrap_or_else(|e| panic!("Failed to mount root `{}`: {e}", self.id));
+        }
+
+        let vnode = call_fiber::<P, VNode<E>>(self.id.clone(), props)
+            .unwrap_or_else(|e| panic!("Failed to render root `{}`: {e}", self.id));
+
+        let mut pass = Pass {
+            renderer,
+            portals: BTreeMap::new(),
+        };
+        let outputs = pass.render_fiber(&self.id, vnode);
+
+        let Pass { renderer, portals } = pass;
+        RenderedRoot {
+            output: combine(renderer, outputs),
+            portals: portals
+                .into_iter()
+                .map(|(target, outputs)| (target, combine(renderer, outputs)))
+                .collect(),
+        }
+    }
+
+    /// Unmounts the root fiber and every component fiber under it.
+    pub fn unmount(self) {
+        if self.fun.is_none() {
+            unmount_fiber(self.id);
+        }
+    }
+}
+
+/// The outputs of a [`Root`], see [`Root::render_with_portals`].
+pub struct RenderedRoot<O> {
+    pub output: O,
+    /// Outputs rendered into portals, by target.
+    pub portals: BTreeMap<String, O>,
+}
+
+/// A single output as is, several ones as a fragment.
+fn combine<R: Renderer>(renderer: &mut R, mut outputs: Vec<RenderedChild<R::Output>>) -> R::Output {
+    if outputs.len() == 1 && outputs[0].key.is_none() {
+        return outputs.pop().expect("checked length").output;
+    }
+    renderer.fragment(outputs)
+}
+
+/// A render of a [`Root`].
+struct Pass<'r, R: Renderer> {
+    renderer: &'r mut R,
+    /// Outputs rendered into portals so far, by target.
+    portals: BTreeMap<String, Vec<RenderedChild<R::Output>>>,
+}
+
+impl<R: Renderer> Pass<'_, R>
+where
+    R::Element: 'static,
+{
+    /// Renders the `vnode` returned by the fiber `id`.
+    ///
+    /// The children of the fiber are reconciled first: components that are gone
+    /// are unmounted and the others are put in the order of `vnode`.
+    fn render_fiber(
+        &mut self,
+        id: &str,
+        vnode: VNode<R::Element>,
+    ) -> Vec<RenderedChild<R::Output>> {
+        let mut children = Vec::new();
+        collect_children(id, "", &vnode, &mut children);
+        reconcile_children(id, &children)
+            .unwrap_or_else(|e| panic!("Failed to reconcile the children of `{id}`: {e}"));
+
+        let mut rendered = Vec::new();
+        self.render_node(id, "", vnode, None, &mut 0, &mut rendered);
+        rendered
+    }
+
+    /// Renders `vnode`, a part of what the fiber `fiber_id` returned.
+    ///
+    /// `scope` holds the keys of the keyed items around `vnode`, see
+    /// [`child_id`], while `key` is the innermost one, given to the renderer.
+    fn render_node(
+        &mut self,
+        fiber_id: &str,
+        scope: &str,
+        vnode: VNode<R::Element>,
+        key: Option<&str>,
+        index: &mut usize,
+        out: &mut Vec<RenderedChild<R::Output>>,
+    ) {
+        match vnode {
+            VNode::Element {
+                element,
+                children: nodes,
+            } => {
+                let mut rendered = Vec::new();
+                for node in nodes {
+                    self.render_node(fiber_id, scope, node, None, index, &mut rendered);
+                }
+                out.push(RenderedChild {
+                    key: key.map(str::to_string),
+                    output: self.renderer.element(element, rendered),
+                });
+            }
+            VNode::Fragment(nodes) => {
+                for node in nodes {
+                    self.render_node(fiber_id, scope, node, key, index, out);
+                }
+            }
+            VNode::Keyed(items) => {
+                for (key, node) in items {
+                    let scope = format!("{scope}{key}:");
+                    self.render_node(fiber_id, &scope, node, Some(&key), index, out);
+                }
+            }
+            VNode::Portal { target, children } => {
+                let mut rendered = Vec::new();
+                for node in children {
+                    self.render_node(fiber_id, scope, node, None, index, &mut rendered);
+                }
+                self.portals.entry(target).or_default().extend(rendered);
+            }
+            VNode::Component(component) => {
+                let id = child_id(fiber_id, scope, &component.key);
+                let vnode = component
+                    .inner
+                    .render(fiber_id, &id, *index)
+                    .unwrap_or_else(|e| panic!("Failed to render component `{id}`: {e}"));
+                *index += 1;
+
+                for mut child in self.render_fiber(&id, vnode) {
+                    if key.is_some() {
+                        child.key = key.map(str::to_string);
+                    }
+                    out.push(child);
+                }
+            }
+        }
+    }
+}
+
+/// Id of the fiber of a component with `key`, rendered by `fiber_id`.
+///
+/// `scope` holds the keys of the keyed items around the component, each
+/// followed by `:`, so a component moves along with its keyed item.
+fn child_id(fiber_id: &str, scope: &str, key: &str) -> String {
+    format!("{fiber_id}/{scope}{key}")
+}
+
+/// Ids of the components in `vnode`, in order.
+///
+/// # Panics
+///
+/// Panics if keyed items share a key.
+fn collect_children<E>(fiber_id: &str, scope: &str, vnode: &VNode<E>, out: &mut Vec<String>) {
+    match vnode {
+        VNode::Element { children, .. }
+        | VNode::Fragment(children)
+        | VNode::Portal { children, .. } => {
+            for node in children {
+                collect_children(fiber_id, scope, node, out);
+            }
+        }
+        VNode::Keyed(items) => {
+            let mut keys = HashSet::new();
+            for (key, node) in items {
+                if !keys.insert(key) {
+                    panic!("Duplicate key `{scope}{key}` among the keyed items of `{fiber_id}`.");
+                }
+                collect_children(fiber_id, &format!("{scope}{key}:"), node, out);
+            }
+        }
+        VNode::Component(component) => out.push(child_id(fiber_id, scope, &component.key)),
+    }
+}
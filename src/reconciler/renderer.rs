@@ -0,0 +1,29 @@
+/// Turns the host elements of a [`VNode`](crate::VNode) tree into the output
+/// of a backend, e.g. widgets or markup.
+///
+/// Components and fragments never reach the renderer: the reconciler renders
+/// them and passes their outputs on as children.
+pub trait Renderer {
+    /// The host element type, `E` in `VNode<E>`.
+    type Element;
+    /// What elements are rendered to.
+    type Output;
+
+    /// Renders a host element from its rendered children.
+    fn element(
+        &mut self,
+        element: Self::Element,
+        children: Vec<RenderedChild<Self::Output>>,
+    ) -> Self::Output;
+
+    /// Combines outputs that don't have a host element around them, e.g. when
+    /// the root component renders a fragment.
+    fn fragment(&mut self, children: Vec<RenderedChild<Self::Output>>) -> Self::Output;
+}
+
+/// The output of a node, with the key of the [`VNode::Keyed`](crate::VNode::Keyed)
+/// item it was rendered from, if any.
+pub struct RenderedChild<O> {
+    pub key: Option<String>,
+    pub output: O,
+}
@@ -0,0 +1,153 @@
+use std::any::TypeId;
+
+use crate::{
+    FiberStoreError,
+    fiber::{FIBER_TREE, Fiber, call_fiber, mount_fiber_at, unmount_fiber},
+};
+
+/// A renderer-agnostic description of the UI, returned by components.
+///
+/// `E` is the host element type of the [`Renderer`](crate::Renderer), e.g. a
+/// widget or a DOM tag. Components are rendered in fibers of their own, so
+/// they keep their hooks across renders.
+pub enum VNode<E> {
+    /// A host element and its children.
+    Element { element: E, children: Vec<VNode<E>> },
+    /// A component, rendered in the child fiber `{parent}/{key}`, or
+    /// `{parent}/{item key}:{key}` inside a [keyed](VNode::keyed) item.
+    Component(ComponentNode<E>),
+    /// Nodes rendered in place of the fragment, in order.
+    Fragment(Vec<VNode<E>>),
+    /// Nodes identified by a key, which is passed to the renderer with their
+    /// output and is part of the fiber ids of the components they contain.
+    Keyed(Vec<(String, VNode<E>)>),
+    /// Nodes rendered into another render target, see [`VNode::portal`].
+    Portal {
+        target: String,
+        children: Vec<VNode<E>>,
+    },
+}
+
+impl<E: 'static> VNode<E> {
+    /// A host element without children.
+    pub fn element(element: impl Into<E>) -> Self {
+        Self::with_children(element, Vec::new())
+    }
+
+    /// A host element with children.
+    pub fn with_children(element: impl Into<E>, children: Vec<Self>) -> Self {
+        Self::Element {
+            element: element.into(),
+            children,
+        }
+    }
+
+    pub fn fragment(children: Vec<Self>) -> Self {
+        Self::Fragment(children)
+    }
+
+    pub fn keyed<K: Into<String>>(items: impl IntoIterator<Item = (K, Self)>) -> Self {
+        Self::Keyed(items.into_iter().map(|(k, v)| (k.into(), v)).collect())
+    }
+
+    /// Renders `children` into the render target `target` instead of in place,
+    /// e.g. to show a modal at the root of the window.
+    ///
+    /// Components in a portal are still children of the fiber declaring it, so
+    /// they read the contexts provided around the portal. Their outputs are
+    /// returned by [`Root::render_with_portals`](crate::Root::render_with_portals).
+    pub fn portal(target: impl Into<String>, children: Vec<Self>) -> Self {
+        Self::Portal {
+            target: target.into(),
+            children,
+        }
+    }
+
+    /// A component rendering `fun` with `props`.
+    ///
+    /// `key` identifies the component among the components rendered by the same
+    /// fiber. The fiber is mounted the first time the key shows up and unmounted
+    /// once its parent renders without it, or with another function under that
+    /// key. Closures count as the same function on every render, and the fiber
+    /// renders the latest one, with its latest captures.
+    pub fn component<P: 'static>(
+        key: impl Into<String>,
+        fun: impl FnMut(P) -> VNode<E> + 'static,
+        props: P,
+    ) -> Self {
+        Self::Component(ComponentNode {
+            key: key.into(),
+            inner: Box::new(TypedComponent { fun, props }),
+        })
+    }
+}
+
+/// A component in a [`VNode`] tree, see [`VNode::component`].
+pub struct ComponentNode<E> {
+    pub(crate) key: String,
+    pub(crate) inner: Box<dyn DynComponent<E>>,
+}
+
+impl<E> ComponentNode<E> {
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+}
+
+pub(crate) trait DynComponent<E> {
+    /// Mounts the fiber at `index` among the children of `parent` if needed,
+    /// and renders it.
+    fn render(
+        self: Box<Self>,
+        parent: &str,
+        id: &str,
+        index: usize,
+    ) -> Result<VNode<E>, FiberStoreError>;
+}
+
+struct TypedComponent<F, P> {
+    fun: F,
+    props: P,
+}
+
+impl<E, F, P> DynComponent<E> for TypedComponent<F, P>
+where
+    E: 'static,
+    F: FnMut(P) -> VNode<E> + 'static,
+    P: 'static,
+{
+    fn render(
+        self: Box<Self>,
+        parent: &str,
+        id: &str,
+        index: usize,
+    ) -> Result<VNode<E>, FiberStoreError> {
+        // Swap in the new function when the same component is rendered again,
+        // so it sees the values captured by this render.
+        let mut fun = Some(self.fun);
+        let mounted = FIBER_TREE.with(|t| {
+            t.borrow().0.get(id).map(|node| {
+                let mut fiber = node.fiber.borrow_mut();
+                match fiber.as_any_mut().downcast_mut::<Fiber<P, VNode<E>>>() {
+                    Some(fiber) if fiber.fun_type == TypeId::of::<F>() => {
+                        fiber.fun = Box::new(fun.take().unwrap());
+                        true
+                    }
+                    _ => false,
+                }
+            })
+        });
+
+        match mounted {
+            Some(true) => {}
+            // Another component took the key, start over.
+            Some(false) => {
+                unmount_fiber(id);
+                mount_fiber_at(parent, id, index, fun.unwrap())?;
+            }
+            None => mount_fiber_at(parent, id, index, fun.unwrap())?,
+        }
+
+        call_fiber(id, self.props)
+    }
+}
@@ -0,0 +1,193 @@
+use std::{any::Any, cell::RefCell, collections::BTreeMap, rc::Rc};
+
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use serde_json::Value;
+
+use crate::{
+    fiber::{FIBER_TREE, HooksState},
+    hooks::read_fiber_state,
+};
+
+/// Serializes a type-erased hook state, registered by the hooks that support it.
+pub(crate) type SerializeFn = fn(&dyn Any) -> Result<Value, String>;
+
+/// Serialized hook state of a fiber tree, created by [`snapshot`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Snapshot {
+    /// Serialized state values, by fiber id and hook index.
+    pub fibers: BTreeMap<String, BTreeMap<usize, Value>>,
+    /// Hook slots that were left out of the snapshot.
+    #[serde(default)]
+    pub skipped: Vec<SkippedSlot>,
+}
+
+/// A hook slot that couldn't be saved by [`snapshot`] or hydrated by [`restore`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SkippedSlot {
+    pub fiber_id: String,
+    pub hook_index: usize,
+    /// Kind of the hook (e.g. `use_ref`), empty if the fiber doesn't exist.
+    pub kind: String,
+    pub reason: String,
+}
+
+/// Slots of a [`Snapshot`] that [`restore`] couldn't hydrate.
+///
+/// Values are deserialized when their hook mounts, so slots whose value
+/// doesn't fit the hook, or that no hook took, are added to the report once
+/// their fiber rendered.
+#[derive(Debug, Clone, Default)]
+pub struct RestoreReport(Rc<RefCell<Vec<SkippedSlot>>>);
+
+impl RestoreReport {
+    /// The slots skipped so far.
+    pub fn skipped(&self) -> Vec<SkippedSlot> {
+        self.0.borrow().clone()
+    }
+
+    /// Whether every slot was hydrated so far.
+    pub fn is_empty(&self) -> bool {
+        self.0.borrow().is_empty()
+    }
+
+    fn push(&self, slot: SkippedSlot) {
+        self.0.borrow_mut().push(slot);
+    }
+}
+
+/// Serializes the state of every [`use_state_serde`](crate::use_state_serde)
+/// hook in the fiber tree.
+///
+/// The value saved is the one returned by the last render. Every other hook is
+/// listed in [`Snapshot::skipped`].
+pub fn snapshot() -> Snapshot {
+    let mut snapshot = Snapshot::default();
+
+    FIBER_TREE.with(|t| {
+        let tree = t.borrow();
+        for (id, node) in &tree.0 {
+            let state_ptr = node.fiber.borrow().state_ptr();
+            let state = unsafe { &*state_ptr };
+
+            let mut slots = BTreeMap::new();
+            for (idx, hook) in state.hooks.iter().enumerate() {
+                let result = match hook.serialize {
+                    Some(serialize) => serialize(&*hook.state),
+                    None => Err("not serializable".to_string()),
+                };
+                match result {
+                    Ok(value) => {
+                        slots.insert(idx, value);
+                    }
+                    Err(reason) => snapshot.skipped.push(SkippedSlot {
+                        fiber_id: id.clone(),
+                        hook_index: idx,
+                        kind: hook.kind.to_string(),
+                        reason,
+                    }),
+                }
+            }
+
+            if !slots.is_empty() {
+                snapshot.fibers.insert(id.clone(), slots);
+            }
+        }
+    });
+
+    snapshot
+        .skipped
+        .sort_by(|a, b| (&a.fiber_id, a.hook_index).cmp(&(&b.fiber_id, b.hook_index)));
+    snapshot
+}
+
+/// Hydrates freshly mounted fibers with the values of a [`Snapshot`].
+///
+/// Must be called after the fibers are mounted and before their first render:
+/// each [`use_state_serde`](crate::use_state_serde) hook then starts from the
+/// saved value instead of its initializer. The returned report lists the
+/// slots of fibers that don't exist or have already rendered, then the values
+/// that couldn't be deserialized or weren't taken by any hook once their
+/// fibers rendered.
+pub fn restore(snapshot: &Snapshot) -> RestoreReport {
+    let report = RestoreReport::default();
+
+    FIBER_TREE.with(|t| {
+        let tree = t.borrow();
+        for (id, slots) in &snapshot.fibers {
+            let reason = match tree.0.get(id) {
+                Some(node) => {
+                    let state_ptr = node.fiber.borrow_mut().state_ptr_mut();
+                    let state = unsafe { &mut *state_ptr };
+                    if state.hooks.is_empty() {
+                        state.hydration = slots.clone().into_iter().collect();
+                        state.hydration_report = Some(report.clone());
+                        continue;
+                    }
+                    "fiber has already rendered"
+                }
+                None => "fiber doesn't exist",
+            };
+
+            for &hook_index in slots.keys() {
+                report.push(SkippedSlot {
+                    fiber_id: id.clone(),
+                    hook_index,
+                    kind: String::new(),
+                    reason: reason.to_string(),
+                });
+            }
+        }
+    });
+
+    report
+}
+
+/// Takes the value restored for the hook being mounted in the current fiber, if any.
+///
+/// A value that can't be deserialized is added to the [`RestoreReport`].
+pub(crate) fn take_hydrated<S: DeserializeOwned>(kind: &str) -> Option<S> {
+    let fiber_state = read_fiber_state("Hydration was requested outside of a fiber.");
+
+    // Called while mounting, so the new hook goes at the end of the list.
+    let idx = fiber_state.hooks.len();
+    let value = fiber_state.hydration.remove(&idx)?;
+
+    match serde_json::from_value(value) {
+        Ok(value) => Some(value),
+        Err(err) => {
+            if let Some(report) = &fiber_state.hydration_report {
+                report.push(SkippedSlot {
+                    fiber_id: fiber_state.fiber_id.clone(),
+                    hook_index: idx,
+                    kind: kind.to_string(),
+                    reason: format!("couldn't deserialize the value: {err}"),
+                });
+            }
+            None
+        }
+    }
+}
+
+/// Reports the restored values that no hook took during the fiber's first
+/// render, and drops them.
+pub(crate) fn finish_hydration(fiber_state: &mut HooksState) {
+    let report = fiber_state.hydration_report.take();
+    let mut left: Vec<usize> = fiber_state.hydration.drain().map(|(idx, _)| idx).collect();
+    let Some(report) = report else {
+        return;
+    };
+
+    left.sort_unstable();
+    for hook_index in left {
+        let (kind, reason) = match fiber_state.hooks.get(hook_index) {
+            Some(hook) => (hook.kind.to_string(), "hook doesn't restore values"),
+            None => (String::new(), "no hook at this slot"),
+        };
+        report.push(SkippedSlot {
+            fiber_id: fiber_state.fiber_id.clone(),
+            hook_index,
+            kind,
+            reason: reason.to_string(),
+        });
+    }
+}
@@ -0,0 +1,247 @@
+//! A fiber tree that can be driven from several threads.
+//!
+//! A [`SyncRuntime`] owns a render thread and the fiber tree living on it.
+//! Handles to the runtime are `Send` and `Sync`: any thread can mount, call
+//! and unmount fibers through them, and the work runs on the render thread, one
+//! call at a time. Props and outputs cross threads, so they must be `Send`.
+//!
+//! Inside the runtime, [`use_ref`] keeps its value in an `Arc<Mutex<_>>` and
+//! [`use_state`] returns a [`SyncSetter`], so both can be handed to worker
+//! threads. A setter doesn't touch the fiber from the worker, it queues the
+//! update for the render thread, which applies it in
+//! [`process_updates`](SyncRuntime::process_updates).
+//!
+//! Hooks state and the handles of the other hooks, like
+//! [`SetStateAction`](crate::SetStateAction), never leave the render thread.
+//!
+//! ```rust
+//! use hooks_rs::sync::{SyncRuntime, SyncSetter, use_state};
+//!
+//! type Loader = (Option<String>, SyncSetter<Option<String>>);
+//!
+//! fn loader(_: ()) -> Loader {
+//!     use_state(|| None)
+//! }
+//!
+//! let runtime = SyncRuntime::new();
+//! runtime.mount_fiber(None, "loader", loader).unwrap();
+//!
+//! let worker = runtime.clone();
+//! std::thread::spawn(move || {
+//!     let (_, set_data) = worker.call_fiber::<(), Loader>("loader", ()).unwrap();
+//!     set_data.set(Some("loaded".into()));
+//! })
+//! .join()
+//! .unwrap();
+//!
+//! runtime.process_updates();
+//! let (data, _) = runtime.call_fiber::<(), Loader>("loader", ()).unwrap();
+//! assert_eq!(data.as_deref(), Some("loaded"));
+//! ```
+
+use std::{
+    any::type_name,
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        Arc, Mutex,
+        mpsc::{self, Sender},
+    },
+    thread::{self, JoinHandle, ThreadId},
+};
+
+use crate::{
+    FiberStoreError,
+    fiber::{self, FIBER_TREE, RemoteSender, process_remote_updates},
+    hooks::{
+        slot::hook_slot,
+        use_state::{RemoteSetter, use_state as use_local_state},
+    },
+};
+
+type Job = Box<dyn FnOnce() + Send>;
+
+/// Handle to a render thread and its fiber tree.
+///
+/// Handles are cheap to clone and can be shared between threads. Dropping the
+/// last one unmounts the fibers, running their cleanups, and stops the render
+/// thread.
+#[derive(Clone)]
+pub struct SyncRuntime {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    /// Sends work to the render thread. Taken on drop to stop it.
+    jobs: Option<Sender<Job>>,
+    /// Sends state updates to the fibers of the render thread.
+    updates: RemoteSender,
+    render_thread: ThreadId,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl SyncRuntime {
+    /// Starts a render thread with an empty fiber tree.
+    pub fn new() -> Self {
+        let (jobs, receiver) = mpsc::channel::<Job>();
+        let (init, updates) = mpsc::channel();
+
+        let handle = thread::Builder::new()
+            .name("hooks-rs render".into())
+            .spawn(move || {
+                init.send(RemoteSender::current())
+                    .expect("the runtime waits for its render thread");
+                for job in receiver {
+                    job();
+                }
+                unmount_roots();
+            })
+            .expect("failed to spawn the render thread");
+
+        SyncRuntime {
+            inner: Arc::new(Inner {
+                jobs: Some(jobs),
+                updates: updates.recv().expect("the render thread just started"),
+                render_thread: handle.thread().id(),
+                handle: Some(handle),
+            }),
+        }
+    }
+
+    /// Runs `f` on the render thread and returns its result.
+    ///
+    /// Calls made from the render thread itself, e.g. by a component, run
+    /// right away.
+    ///
+    /// # Panics
+    ///
+    /// Panics with the same payload if `f` panics.
+    pub fn run<T>(&self, f: impl FnOnce() -> T + Send + 'static) -> T
+    where
+        T: Send + 'static,
+    {
+        if thread::current().id() == self.inner.render_thread {
+            return f();
+        }
+
+        let (result, receiver) = mpsc::channel();
+        let job: Job = Box::new(move || {
+            let _ = result.send(panic::catch_unwind(AssertUnwindSafe(f)));
+        });
+        self.inner
+            .jobs
+            .as_ref()
+            .expect("the sender lives as long as the runtime")
+            .send(job)
+            .expect("the render thread lives as long as the runtime");
+
+        match receiver.recv().expect("jobs always send their result") {
+            Ok(value) => value,
+            Err(payload) => panic::resume_unwind(payload),
+        }
+    }
+
+    /// [`mount_fiber`](crate::mount_fiber) in the runtime's fiber tree.
+    pub fn mount_fiber<P, R>(
+        &self,
+        parent: Option<String>,
+        id: impl Into<String>,
+        fun: impl FnMut(P) -> R + Send + 'static,
+    ) -> Result<(), FiberStoreError>
+    where
+        P: 'static,
+        R: 'static,
+    {
+        let id = id.into();
+        self.run(move || fiber::mount_fiber(parent, id, fun))
+    }
+
+    /// [`call_fiber`](crate::call_fiber) in the runtime's fiber tree.
+    pub fn call_fiber<P, R>(&self, id: impl Into<String>, props: P) -> Result<R, FiberStoreError>
+    where
+        P: Send + 'static,
+        R: Send + 'static,
+    {
+        let id = id.into();
+        self.run(move || fiber::call_fiber(id, props))
+    }
+
+    /// [`unmount_fiber`](crate::unmount_fiber) in the runtime's fiber tree.
+    pub fn unmount_fiber(&self, id: impl Into<String>) {
+        let id = id.into();
+        self.run(move || fiber::unmount_fiber(id));
+    }
+
+    /// Whether updates are waiting for [`process_updates`](Self::process_updates).
+    pub fn has_pending_updates(&self) -> bool {
+        self.inner.updates.has_pending()
+    }
+
+    /// Applies the queued updates on the render thread, in the order they were
+    /// sent, as a single [`batch`](crate::batch), and returns how many there were.
+    ///
+    /// Updates to fibers that were unmounted since are dropped.
+    pub fn process_updates(&self) -> usize {
+        self.run(process_remote_updates)
+    }
+}
+
+impl Default for SyncRuntime {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for Inner {
+    fn drop(&mut self) {
+        // Closing the channel ends the render thread's loop.
+        drop(self.jobs.take());
+
+        // A component may hold the last handle, the thread can't wait for itself.
+        if thread::current().id() != self.render_thread
+            && let Some(handle) = self.handle.take()
+        {
+            let _ = handle.join();
+        }
+    }
+}
+
+/// Unmounts every fiber of the current thread's tree.
+fn unmount_roots() {
+    let roots: Vec<String> = FIBER_TREE.with(|t| {
+        let tree = t.borrow();
+        tree.0
+            .iter()
+            .filter(|(_, node)| node.parent.is_none())
+            .map(|(id, _)| id.clone())
+            .collect()
+    });
+    for root in roots {
+        fiber::unmount_fiber(root);
+    }
+}
+
+/// Like [`use_ref`](crate::use_ref), but the value can be shared with other threads.
+#[track_caller]
+pub fn use_ref<S: Send + 'static>(initial_value: S) -> Arc<Mutex<S>> {
+    let slot = hook_slot("sync::use_ref", type_name::<S>(), || {
+        Arc::new(Mutex::new(initial_value))
+    });
+    Arc::clone(&slot)
+}
+
+/// Setter returned by [`sync::use_state`](use_state).
+///
+/// Updates are queued for the thread that rendered the hook, even when set
+/// from that thread, and applied by [`SyncRuntime::process_updates`].
+pub type SyncSetter<S> = RemoteSetter<S>;
+
+/// Like [`use_state`](crate::use_state), but the setter can be sent to other
+/// threads, see [`SyncSetter`].
+#[track_caller]
+pub fn use_state<S>(initial: impl FnOnce() -> S) -> (S, SyncSetter<S>)
+where
+    S: Clone + Send + 'static,
+{
+    let (state, setter) = use_local_state(initial);
+    (state, setter.remote())
+}
@@ -0,0 +1,178 @@
+//! Helpers to test components in isolation.
+//!
+//! ```rust
+//! use hooks_rs::{testing::TestHarness, use_state};
+//!
+//! fn counter(step: i32) -> i32 {
+//!     let (count, set_count) = use_state(|| 0);
+//!     set_count.update(move |prev| prev + step);
+//!     count
+//! }
+//!
+//! let mut harness = TestHarness::new(counter);
+//! assert_eq!(harness.render(2), 0);
+//! assert_eq!(harness.rerender(), 2);
+//! harness.assert_render_count(2);
+//! ```
+
+use std::marker::PhantomData;
+
+use crate::fiber::{FIBER_TREE, FiberTree, batch, call_fiber, mount_fiber, unmount_fiber};
+
+const ROOT_ID: &str = "root";
+
+/// Mounts a component in a fiber tree of its own, so tests don't share fiber ids.
+///
+/// The component is mounted with the id `root`. Every fiber mounted while the
+/// harness renders, e.g. the component's children, goes into the harness's tree.
+/// Dropping the harness unmounts them, running their cleanups.
+pub struct TestHarness<P, R> {
+    tree: FiberTree,
+    props: Option<P>,
+    mounted: bool,
+    _output: PhantomData<fn() -> R>,
+}
+
+impl<P, R> TestHarness<P, R>
+where
+    P: Clone + 'static,
+    R: 'static,
+{
+    /// Mounts `component` as the root of a new, isolated fiber tree.
+    pub fn new(component: impl FnMut(P) -> R + 'static) -> Self {
+        let mut harness = TestHarness {
+            tree: FiberTree::new(),
+            props: None,
+            mounted: true,
+            _output: PhantomData,
+        };
+        harness
+            .enter(|| mount_fiber(None, ROOT_ID, component))
+            .expect("the harness tree is empty");
+        harness
+    }
+
+    /// Renders the component with `props`.
+    pub fn render(&mut self, props: P) -> R {
+        self.props = Some(props.clone());
+        self.enter(|| call_fiber::<P, R>(ROOT_ID, props))
+            .expect("The component was unmounted.")
+    }
+
+    /// Renders the component again with the last props.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the component was never rendered.
+    pub fn rerender(&mut self) -> R {
+        let props = self
+            .props
+            .clone()
+            .expect("`rerender` was called before `render`.");
+        self.render(props)
+    }
+
+    /// Runs `f` as a single batch, then renders the component again if it has
+    /// state updates, so their effects run too.
+    pub fn act<T>(&mut self, f: impl FnOnce() -> T) -> T {
+        let result = self.enter(|| batch(f));
+        if self.props.is_some() && self.is_dirty() {
+            self.rerender();
+        }
+        result
+    }
+
+    /// Whether the component has state updates that haven't been rendered yet.
+    pub fn is_dirty(&self) -> bool {
+        self.tree.0.get(ROOT_ID).is_some_and(|node| node.is_dirty())
+    }
+
+    /// Number of times the component rendered.
+    pub fn render_count(&self) -> u64 {
+        self.root_state(|state| state.render_count)
+    }
+
+    /// Number of times an effect ran, in the component and in its children.
+    pub fn effect_count(&self) -> u64 {
+        self.tree
+            .0
+            .values()
+            .map(|node| {
+                let state_ptr = node.fiber.borrow().state_ptr();
+                unsafe { (*state_ptr).effect_count }
+            })
+            .sum()
+    }
+
+    /// Asserts the component rendered `expected` times.
+    #[track_caller]
+    pub fn assert_render_count(&self, expected: u64) {
+        assert_eq!(
+            self.render_count(),
+            expected,
+            "Expected the component to render {expected} time(s)."
+        );
+    }
+
+    /// Asserts effects ran `expected` times, in the component and in its children.
+    #[track_caller]
+    pub fn assert_effect_count(&self, expected: u64) {
+        assert_eq!(
+            self.effect_count(),
+            expected,
+            "Expected effects to run {expected} time(s)."
+        );
+    }
+
+    fn root_state<T>(&self, f: impl FnOnce(&crate::HooksState) -> T) -> T {
+        let node = self
+            .tree
+            .0
+            .get(ROOT_ID)
+            .expect("The component was unmounted.");
+        let state_ptr = node.fiber.borrow().state_ptr();
+        f(unsafe { &*state_ptr })
+    }
+}
+
+impl<P, R> TestHarness<P, R> {
+    /// Unmounts the component and its children, running their cleanups.
+    ///
+    /// Also done when the harness is dropped.
+    pub fn unmount(&mut self) {
+        if std::mem::take(&mut self.mounted) {
+            self.enter(|| unmount_fiber(ROOT_ID));
+        }
+    }
+
+    /// Runs `f` with the harness's tree as the thread's fiber tree.
+    fn enter<T>(&mut self, f: impl FnOnce() -> T) -> T {
+        /// Puts the tree back, even if `f` panics.
+        struct Swap<'a>(&'a mut FiberTree);
+
+        impl Swap<'_> {
+            fn swap(&mut self) {
+                FIBER_TREE.with(|t| std::mem::swap(&mut *t.borrow_mut(), self.0));
+            }
+        }
+
+        impl Drop for Swap<'_> {
+            fn drop(&mut self) {
+                self.swap();
+            }
+        }
+
+        let mut swap = Swap(&mut self.tree);
+        swap.swap();
+        f()
+    }
+}
+
+impl<P, R> Drop for TestHarness<P, R> {
+    fn drop(&mut self) {
+        // Cleanups could panic again while a failed test unwinds.
+        if !std::thread::panicking() {
+            self.unmount();
+        }
+    }
+}
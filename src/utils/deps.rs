@@ -6,7 +6,7 @@ pub trait DynEq: Any {
 
 impl<T: PartialEq + 'static> DynEq for T {
     fn eq_dyn(&self, other: &dyn Any) -> bool {
-        other.downcast_ref::<T>().map_or(false, |o| self == o)
+        other.downcast_ref::<T>().is_some_and(|o| self == o)
     }
 }
 
@@ -21,3 +21,98 @@ pub(crate) fn deps_changed(old: &[Box<dyn DynEq>], new: &[Box<dyn DynEq>]) -> bo
     }
     false
 }
+
+/// A dependency list, compared between renders to decide whether a hook re-runs.
+///
+/// - `()`: never changes, the hook only runs on mount.
+/// - `(a, b, ..)`: tuples of up to 12 `PartialEq + Clone` values, compared without boxing.
+/// - [`EveryRender`]: always changes, the hook runs after every render.
+/// - `Vec<Box<dyn DynEq>>`: the boxed form, built with [`deps!`](crate::deps).
+pub trait Deps: 'static {
+    /// Returns whether the dependencies differ from those of the previous render.
+    fn changed(&self, prev: &Self) -> bool;
+
+    /// Index of the first dependency that differs from the previous render, if known.
+    ///
+    /// Only used for diagnostics.
+    fn changed_dep(&self, prev: &Self) -> Option<usize> {
+        let _ = prev;
+        None
+    }
+}
+
+/// Dependencies that change on every render.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct EveryRender;
+
+impl Deps for EveryRender {
+    fn changed(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl Deps for () {
+    fn changed(&self, _: &Self) -> bool {
+        false
+    }
+}
+
+impl Deps for Vec<Box<dyn DynEq>> {
+    fn changed(&self, prev: &Self) -> bool {
+        deps_changed(prev, self)
+    }
+
+    fn changed_dep(&self, prev: &Self) -> Option<usize> {
+        (0..self.len().max(prev.len())).find(|&i| match (prev.get(i), self.get(i)) {
+            (Some(o), Some(n)) => !o.eq_dyn(&**n),
+            _ => true,
+        })
+    }
+}
+
+macro_rules! impl_deps_for_tuple {
+    ($($name:ident $idx:tt),+) => {
+        impl<$($name: PartialEq + Clone + 'static),+> Deps for ($($name,)+) {
+            fn changed(&self, prev: &Self) -> bool {
+                self != prev
+            }
+
+            fn changed_dep(&self, prev: &Self) -> Option<usize> {
+                $(
+                    if self.$idx != prev.$idx {
+                        return Some($idx);
+                    }
+                )+
+                None
+            }
+        }
+    };
+}
+
+impl_deps_for_tuple!(A 0);
+impl_deps_for_tuple!(A 0, B 1);
+impl_deps_for_tuple!(A 0, B 1, C 2);
+impl_deps_for_tuple!(A 0, B 1, C 2, D 3);
+impl_deps_for_tuple!(A 0, B 1, C 2, D 3, E 4);
+impl_deps_for_tuple!(A 0, B 1, C 2, D 3, E 4, F 5);
+impl_deps_for_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+impl_deps_for_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+impl_deps_for_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8);
+impl_deps_for_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9);
+impl_deps_for_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10);
+impl_deps_for_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11);
+
+/// Builds a boxed dependency list (`Vec<Box<dyn DynEq>>`) from values of any `PartialEq` types.
+///
+/// ```rust
+/// use hooks_rs::{DynEq, deps};
+///
+/// let deps: Vec<Box<dyn DynEq>> = deps![1, "two", 3.0];
+/// assert_eq!(deps.len(), 3);
+/// ```
+#[macro_export]
+macro_rules! deps {
+    ($($dep:expr),* $(,)?) => {
+        ::std::vec![$(::std::boxed::Box::new($dep) as ::std::boxed::Box<dyn $crate::DynEq>),*]
+    };
+}
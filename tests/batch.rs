@@ -0,0 +1,90 @@
+use hooks_rs::{
+    SetStateAction, batch, call_fiber, get_dirty_ids, is_fiber_dirty, mount_fiber, unmount_fiber,
+    use_state,
+};
+
+type Setters = (SetStateAction<i32>, SetStateAction<String>);
+
+fn component(_: ()) -> (i32, String, Setters) {
+    let (count, set_count) = use_state(|| 0);
+    let (text, set_text) = use_state(|| String::from("hi"));
+    (count, text, (set_count, set_text))
+}
+
+#[test]
+fn updates_apply_when_batch_ends() {
+    mount_fiber(None, "root", component).unwrap();
+
+    let (_, _, (set_count, set_text)) =
+        call_fiber::<(), (i32, String, Setters)>("root", ()).unwrap();
+    assert!(!is_fiber_dirty("root").unwrap());
+
+    batch(|| {
+        set_count.update(|prev| prev + 1);
+        set_count.update(|prev| prev * 10);
+        set_text.update(|prev| format!("{prev}!"));
+
+        // Nothing is applied until the batch ends.
+        assert!(!is_fiber_dirty("root").unwrap());
+    });
+
+    assert_eq!(get_dirty_ids(), ["root"]);
+
+    let (count, text, _) = call_fiber::<(), (i32, String, Setters)>("root", ()).unwrap();
+    assert_eq!((count, text.as_str()), (10, "hi!"));
+    assert!(!is_fiber_dirty("root").unwrap());
+}
+
+#[test]
+fn nested_batches_apply_once_outermost_ends() {
+    mount_fiber(None, "root", component).unwrap();
+
+    let (_, _, (set_count, _)) = call_fiber::<(), (i32, String, Setters)>("root", ()).unwrap();
+
+    batch(|| {
+        batch(|| set_count.update(|prev| prev + 1));
+        assert!(!is_fiber_dirty("root").unwrap());
+    });
+
+    assert!(is_fiber_dirty("root").unwrap());
+}
+
+#[test]
+fn updates_during_render_are_deferred() {
+    fn counter(_: ()) -> i32 {
+        let (count, set_count) = use_state(|| 0);
+        set_count.update(|prev| prev + 1);
+
+        // Still pending: the fiber is only marked dirty once the render ends.
+        assert!(!is_fiber_dirty("root").unwrap());
+        count
+    }
+
+    mount_fiber(None, "root", counter).unwrap();
+
+    assert_eq!(call_fiber::<(), i32>("root", ()).unwrap(), 0);
+    assert!(is_fiber_dirty("root").unwrap());
+    assert_eq!(call_fiber::<(), i32>("root", ()).unwrap(), 1);
+}
+
+#[test]
+fn updates_of_fibers_unmounted_in_the_batch_are_dropped() {
+    mount_fiber(None, "root", component).unwrap();
+    let (_, _, (set_count, _)) = call_fiber::<(), (i32, String, Setters)>("root", ()).unwrap();
+
+    batch(|| {
+        set_count.set(1);
+        unmount_fiber("root");
+    });
+
+    // A fiber mounted again with the same id doesn't get the update either.
+    mount_fiber(None, "root", component).unwrap();
+    set_count.set(2);
+    assert!(get_dirty_ids().is_empty());
+    assert_eq!(
+        call_fiber::<(), (i32, String, Setters)>("root", ())
+            .unwrap()
+            .0,
+        0
+    );
+}
@@ -0,0 +1,67 @@
+use hooks_rs::{call_fiber, mount_fiber, replace_fiber_fn, use_state};
+use std::{cell::RefCell, rc::Rc};
+
+#[test]
+fn closure_fibers_capture_data() {
+    let greeting = String::from("hello");
+    mount_fiber(None, "root", move |name: &'static str| {
+        format!("{greeting}, {name}")
+    })
+    .unwrap();
+
+    assert_eq!(
+        call_fiber::<&'static str, String>("root", "world").unwrap(),
+        "hello, world"
+    );
+}
+
+#[test]
+fn closure_fibers_keep_their_captures_between_renders() {
+    let mut renders = 0;
+    mount_fiber(None, "root", move |_: ()| {
+        renders += 1;
+        renders
+    })
+    .unwrap();
+
+    assert_eq!(call_fiber::<(), i32>("root", ()).unwrap(), 1);
+    assert_eq!(call_fiber::<(), i32>("root", ()).unwrap(), 2);
+}
+
+#[test]
+fn closure_fibers_use_hooks() {
+    let log = Rc::new(RefCell::new(Vec::new()));
+
+    let fiber_log = log.clone();
+    mount_fiber(None, "root", move |step: i32| {
+        let (count, set_count) = use_state(|| 0);
+        set_count.update(move |prev| prev + step);
+        fiber_log.borrow_mut().push(count);
+    })
+    .unwrap();
+
+    call_fiber::<i32, ()>("root", 2).unwrap();
+    call_fiber::<i32, ()>("root", 3).unwrap();
+    call_fiber::<i32, ()>("root", 0).unwrap();
+    assert_eq!(*log.borrow(), [0, 2, 5]);
+}
+
+#[test]
+fn closures_can_replace_fibers() {
+    fn counter(_: ()) -> i32 {
+        let (count, set_count) = use_state(|| 0);
+        set_count.update(|prev| prev + 1);
+        count
+    }
+
+    mount_fiber(None, "root", counter).unwrap();
+    call_fiber::<(), i32>("root", ()).unwrap();
+
+    let offset = 100;
+    replace_fiber_fn("root", move |_: ()| {
+        let (count, _) = use_state(|| 0);
+        count + offset
+    })
+    .unwrap();
+    assert_eq!(call_fiber::<(), i32>("root", ()).unwrap(), 101);
+}
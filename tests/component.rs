@@ -0,0 +1,95 @@
+use hooks_rs::{
+    Component, FiberHandle, component, get_parent_id, testing::TestHarness, use_effect, use_state,
+};
+
+#[component]
+fn Counter(step: i32, label: String) -> String {
+    let (count, set_count) = use_state(|| 0);
+    set_count.update(move |prev| prev + step);
+    format!("{label}: {count}")
+}
+
+#[component]
+fn Header() -> &'static str {
+    "header"
+}
+
+#[allow(non_snake_case)]
+#[component]
+fn with_effect(dep: i32) -> usize {
+    let (runs, set_runs) = use_state(|| 0);
+    use_effect(&mut || set_runs.update(|prev| prev + 1), (dep,));
+    runs
+}
+
+#[test]
+fn props_struct_and_handle_are_generated() {
+    let counter = Counter::mount(None, "root").unwrap();
+    let props = || CounterProps {
+        step: 2,
+        label: "count".into(),
+    };
+
+    assert_eq!(counter.call(props()).unwrap(), "count: 0");
+    assert_eq!(counter.call(props()).unwrap(), "count: 2");
+}
+
+#[test]
+fn components_without_params_take_unit_props() {
+    let header: FiberHandle<(), &'static str> = Header::mount(None, "root").unwrap();
+    assert_eq!(header.call(()).unwrap(), "header");
+}
+
+#[test]
+fn snake_case_components_and_children() {
+    Header::mount(None, "root").unwrap();
+    let child = with_effect::mount(Some("root".into()), "root/child").unwrap();
+
+    // Strict mode runs mount effects a second time.
+    let runs = if cfg!(feature = "strict") { 2 } else { 1 };
+
+    assert_eq!(get_parent_id(child.id()).unwrap().as_deref(), Some("root"));
+    assert_eq!(child.call(WithEffectProps { dep: 1 }).unwrap(), 0);
+    assert_eq!(child.call(WithEffectProps { dep: 1 }).unwrap(), runs);
+    assert_eq!(child.call(WithEffectProps { dep: 1 }).unwrap(), runs);
+
+    child.unmount();
+    assert!(get_parent_id("root/child").is_err());
+}
+
+/// Joins its words, lowercased.
+#[component]
+fn Sentence(mut words: Vec<String>, ref separator: String) -> String {
+    words
+        .iter_mut()
+        .for_each(|word| *word = word.to_lowercase());
+    words.join(separator)
+}
+
+#[test]
+fn mut_and_ref_parameters_are_kept() {
+    let sentence = Sentence::mount(None, "root").unwrap();
+    let props = SentenceProps {
+        words: vec!["Hello".into(), "World".into()],
+        separator: " ".into(),
+    };
+    assert_eq!(sentence.call(props).unwrap(), "hello world");
+}
+
+#[component(derive(Clone, Debug, PartialEq))]
+fn Parsed(input: String) -> Result<i32, std::num::ParseIntError> {
+    // Hooks before an early exit always run.
+    let (offset, _) = use_state(|| 1);
+    let value: i32 = input.parse()?;
+    Ok(value + offset)
+}
+
+#[test]
+fn attributes_are_forwarded_to_the_props_struct() {
+    let props = ParsedProps { input: "2".into() };
+    assert_eq!(props.clone(), props);
+
+    let mut harness = TestHarness::new(Parsed::render);
+    assert_eq!(harness.render(props), Ok(3));
+    assert!(harness.render(ParsedProps { input: "x".into() }).is_err());
+}
@@ -0,0 +1,158 @@
+use hooks_rs::{
+    History, call_fiber, is_fiber_dirty, mount_fiber, unmount_fiber, use_state, use_state_immediate,
+};
+
+thread_local! {
+    static SET_TEXT: std::cell::Cell<Option<hooks_rs::SetStateAction<String>>> =
+        const { std::cell::Cell::new(None) };
+}
+
+fn editor(_: ()) -> String {
+    let (text, set_text) = use_state(String::new);
+    SET_TEXT.with(|s| s.set(Some(set_text)));
+    text
+}
+
+fn type_text(text: &str) {
+    let set_text = SET_TEXT.with(|s| s.get()).unwrap();
+    let text = text.to_string();
+    set_text.update(move |prev| format!("{prev}{text}"));
+}
+
+fn render() -> String {
+    call_fiber::<(), String>("editor", ()).unwrap()
+}
+
+#[test]
+fn records_applied_changes() {
+    mount_fiber(None, "editor", editor).unwrap();
+    let history = History::start();
+
+    render();
+    type_text("a");
+    type_text("b");
+    assert!(history.entries().is_empty());
+
+    // Queued updates are recorded once they are applied.
+    assert_eq!(render(), "ab");
+
+    let entries = history.entries();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[1].fiber_id, "editor");
+    assert_eq!(entries[1].hook_index, 0);
+    assert_eq!(entries[1].old_value::<String>().unwrap(), "a");
+    assert_eq!(entries[1].new_value::<String>().unwrap(), "ab");
+    assert_eq!(entries[1].new_value::<i32>(), None);
+}
+
+#[test]
+fn undo_redo_and_jump_write_values_back() {
+    mount_fiber(None, "editor", editor).unwrap();
+    let history = History::start();
+
+    render();
+    for text in ["a", "b", "c"] {
+        type_text(text);
+        render();
+    }
+    assert_eq!(history.position(), 3);
+
+    assert!(history.undo());
+    assert!(is_fiber_dirty("editor").unwrap());
+    assert_eq!(render(), "ab");
+
+    assert!(history.redo());
+    assert_eq!(render(), "abc");
+    assert!(!history.redo());
+
+    history.jump_to(0);
+    assert_eq!(render(), "");
+    assert!(!history.undo());
+
+    history.jump_to(2);
+    assert_eq!(render(), "ab");
+
+    // A new change drops the undone ones.
+    type_text("x");
+    assert_eq!(render(), "abx");
+    assert_eq!(history.entries().len(), 3);
+    assert!(!history.redo());
+}
+
+#[test]
+fn immediate_updates_are_recorded() {
+    fn counter(_: ()) -> i32 {
+        let (count, set_count) = use_state_immediate(|| 0);
+        if count < 2 {
+            set_count.update(|prev| prev + 1);
+        }
+        count
+    }
+
+    mount_fiber(None, "counter", counter).unwrap();
+    let history = History::start();
+
+    call_fiber::<(), i32>("counter", ()).unwrap();
+    call_fiber::<(), i32>("counter", ()).unwrap();
+    assert_eq!(history.position(), 2);
+
+    history.jump_to(0);
+    assert_eq!(call_fiber::<(), i32>("counter", ()).unwrap(), 0);
+}
+
+#[test]
+fn unmounted_fibers_are_skipped() {
+    mount_fiber(None, "editor", editor).unwrap();
+    let history = History::start();
+
+    render();
+    type_text("a");
+    render();
+
+    unmount_fiber("editor");
+    assert!(history.undo());
+    assert_eq!(history.position(), 0);
+    history.stop();
+}
+
+#[test]
+fn remounted_fibers_are_skipped() {
+    mount_fiber(None, "editor", editor).unwrap();
+    let history = History::start();
+
+    render();
+    type_text("a");
+    render();
+
+    // Same id, new state: the recorded change belongs to the old mount.
+    unmount_fiber("editor");
+    mount_fiber(None, "editor", editor).unwrap();
+    render();
+
+    assert!(history.undo());
+    assert!(!is_fiber_dirty("editor").unwrap());
+    assert!(history.redo());
+    assert_eq!(render(), "");
+}
+
+#[test]
+fn dropping_the_handle_stops_recording() {
+    let history = History::start();
+    assert!(History::is_recording());
+    drop(history);
+    assert!(!History::is_recording());
+
+    // An older handle doesn't stop a newer recording.
+    let first = History::start();
+    let second = History::start();
+    drop(first);
+    assert!(History::is_recording());
+    second.stop();
+    assert!(!History::is_recording());
+}
+
+#[test]
+#[should_panic(expected = "Can't jump to change 1, only 0 were recorded.")]
+fn jumping_past_the_end_panics() {
+    History::start().jump_to(1);
+}
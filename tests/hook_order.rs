@@ -0,0 +1,104 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use hooks_rs::{call_fiber, mount_fiber, use_effect, use_ref, use_state};
+
+#[test]
+fn same_order_is_accepted() {
+    fn component(_: ()) -> i32 {
+        let (count, _) = use_state(|| 1);
+        let r = use_ref(2);
+        let value = *r.borrow();
+        count + value
+    }
+
+    mount_fiber(None, "root", component).unwrap();
+
+    for _ in 0..3 {
+        assert_eq!(call_fiber::<(), i32>("root", ()).unwrap(), 3);
+    }
+}
+
+#[test]
+#[cfg(any(debug_assertions, feature = "verify-hooks"))]
+#[should_panic(expected = "slot 1: `use_effect` was skipped")]
+fn skipped_hook_is_reported() {
+    static SKIP: AtomicBool = AtomicBool::new(false);
+
+    fn component(_: ()) {
+        let _ = use_state(|| 0);
+        if !SKIP.load(Ordering::Relaxed) {
+            use_effect(&mut || {}, ());
+        }
+    }
+
+    mount_fiber(None, "root", component).unwrap();
+
+    call_fiber::<(), ()>("root", ()).unwrap();
+    SKIP.store(true, Ordering::Relaxed);
+    call_fiber::<(), ()>("root", ()).unwrap();
+}
+
+#[test]
+#[cfg(any(debug_assertions, feature = "verify-hooks"))]
+#[should_panic(expected = "slot 1: `use_ref` was added")]
+fn extra_hook_is_reported() {
+    static EXTRA: AtomicBool = AtomicBool::new(false);
+
+    fn component(_: ()) {
+        let _ = use_state(|| 0);
+        if EXTRA.load(Ordering::Relaxed) {
+            let _ = use_ref(0);
+        }
+    }
+
+    mount_fiber(None, "root", component).unwrap();
+
+    call_fiber::<(), ()>("root", ()).unwrap();
+    EXTRA.store(true, Ordering::Relaxed);
+    call_fiber::<(), ()>("root", ()).unwrap();
+}
+
+#[test]
+#[cfg(any(debug_assertions, feature = "verify-hooks"))]
+#[should_panic(
+    expected = "slot 0: expected `use_state`, got `use_ref`\n  slot 1: `use_effect` was skipped"
+)]
+fn changed_kind_is_reported_with_the_full_diff() {
+    static CHANGE: AtomicBool = AtomicBool::new(false);
+
+    fn component(_: ()) {
+        if CHANGE.load(Ordering::Relaxed) {
+            let _ = use_ref(0);
+        } else {
+            let _ = use_state(|| 0);
+            use_effect(&mut || {}, ());
+        }
+    }
+
+    mount_fiber(None, "root", component).unwrap();
+
+    call_fiber::<(), ()>("root", ()).unwrap();
+    CHANGE.store(true, Ordering::Relaxed);
+    call_fiber::<(), ()>("root", ()).unwrap();
+}
+
+#[test]
+#[cfg(any(debug_assertions, feature = "verify-hooks"))]
+#[should_panic(expected = "slot 0: expected `use_state` of `i32`, got `alloc::string::String`")]
+fn changed_state_type_is_reported() {
+    static CHANGE: AtomicBool = AtomicBool::new(false);
+
+    fn component(_: ()) {
+        if CHANGE.load(Ordering::Relaxed) {
+            let _ = use_state(String::new);
+        } else {
+            let _ = use_state(|| 0);
+        }
+    }
+
+    mount_fiber(None, "root", component).unwrap();
+
+    call_fiber::<(), ()>("root", ()).unwrap();
+    CHANGE.store(true, Ordering::Relaxed);
+    call_fiber::<(), ()>("root", ()).unwrap();
+}
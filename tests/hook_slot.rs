@@ -0,0 +1,144 @@
+use hooks_rs::{HookSlotRef, call_fiber, mount_fiber, unmount_fiber, use_hook_slot, use_state};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+#[test]
+fn slot_state_persists_across_renders() {
+    fn use_render_count() -> (u32, bool) {
+        let count = use_hook_slot(|| 0u32);
+        *count.borrow_mut() += 1;
+        (*count.borrow(), count.is_mount())
+    }
+
+    fn component(_: ()) -> (u32, bool) {
+        use_render_count()
+    }
+
+    mount_fiber(None, "root", component).unwrap();
+
+    let render = || call_fiber::<(), (u32, bool)>("root", ()).unwrap();
+
+    assert_eq!(render(), (1, true));
+    assert_eq!(render(), (2, false));
+    assert_eq!(render(), (3, false));
+}
+
+#[test]
+fn slot_index_follows_call_order() {
+    fn component(_: ()) -> usize {
+        let _ = use_state(|| 0);
+        let slot = use_hook_slot(|| "custom");
+        slot.index()
+    }
+
+    mount_fiber(None, "root", component).unwrap();
+    assert_eq!(call_fiber::<(), usize>("root", ()).unwrap(), 1);
+}
+
+#[test]
+fn on_commit_runs_after_every_render() {
+    static COMMITS: AtomicU64 = AtomicU64::new(0);
+
+    fn component(_: ()) {
+        let mut slot = use_hook_slot(|| 0u64);
+        slot.on_commit(|renders| {
+            *renders += 1;
+            COMMITS.store(*renders, Ordering::Relaxed);
+        });
+
+        // The commit callback hasn't run for this render yet.
+        assert_eq!(*slot.borrow(), COMMITS.load(Ordering::Relaxed));
+    }
+
+    mount_fiber(None, "root", component).unwrap();
+
+    call_fiber::<(), ()>("root", ()).unwrap();
+    assert_eq!(COMMITS.load(Ordering::Relaxed), 1);
+
+    call_fiber::<(), ()>("root", ()).unwrap();
+    assert_eq!(COMMITS.load(Ordering::Relaxed), 2);
+}
+
+#[test]
+fn on_unmount_receives_the_slot_state() {
+    static RELEASED: AtomicU64 = AtomicU64::new(0);
+
+    fn use_resource(handle: u64) {
+        let mut slot = use_hook_slot(|| handle);
+        if slot.is_mount() {
+            slot.on_unmount(|handle| RELEASED.store(*handle, Ordering::Relaxed));
+        }
+    }
+
+    fn component(handle: u64) {
+        use_resource(handle);
+    }
+
+    mount_fiber(None, "root", component).unwrap();
+
+    call_fiber::<u64, ()>("root", 42).unwrap();
+    call_fiber::<u64, ()>("root", 7).unwrap();
+    assert_eq!(RELEASED.load(Ordering::Relaxed), 0);
+
+    unmount_fiber("root");
+    assert_eq!(RELEASED.load(Ordering::Relaxed), 42);
+}
+
+#[test]
+#[cfg_attr(
+    any(debug_assertions, feature = "verify-hooks"),
+    should_panic(expected = "slot 0: expected `use_state`, got `u32`")
+)]
+#[cfg_attr(
+    not(any(debug_assertions, feature = "verify-hooks")),
+    should_panic(expected = "Expected `u32` hook at slot 0, but got `use_state`.")
+)]
+fn slot_type_mismatch_panics() {
+    fn component(first: bool) {
+        if first {
+            let _ = use_state(|| 0);
+        } else {
+            let _ = use_hook_slot(|| 0u32);
+        }
+    }
+
+    mount_fiber(None, "root", component).unwrap();
+
+    call_fiber::<bool, ()>("root", true).unwrap();
+    call_fiber::<bool, ()>("root", false).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "was called outside of a fiber")]
+fn slot_outside_of_fiber_panics() {
+    let _ = use_hook_slot(|| 0u32);
+}
+
+#[test]
+#[should_panic(expected = "already mutably borrowed")]
+fn overlapping_borrows_of_a_slot_panic() {
+    fn component(_: ()) -> HookSlotRef<u32> {
+        use_hook_slot(|| 0u32)
+    }
+
+    mount_fiber(None, "root", component).unwrap();
+    let first = call_fiber::<(), HookSlotRef<u32>>("root", ()).unwrap();
+    let second = call_fiber::<(), HookSlotRef<u32>>("root", ()).unwrap();
+
+    let _value = first.borrow_mut();
+    let _ = second.borrow();
+}
+
+#[test]
+#[should_panic(expected = "Hook slot 0 was used after its fiber was unmounted.")]
+fn handle_used_after_unmount_panics() {
+    fn component(_: ()) -> HookSlotRef<u32> {
+        use_hook_slot(|| 0u32)
+    }
+
+    mount_fiber(None, "root", component).unwrap();
+    let slot = call_fiber::<(), HookSlotRef<u32>>("root", ()).unwrap();
+    assert_eq!(*slot.borrow(), 0);
+
+    unmount_fiber("root");
+    let _ = slot.borrow();
+}
@@ -0,0 +1,122 @@
+use hooks_rs::{
+    FiberStoreError, SetStateAction, call_fiber, is_fiber_dirty, mount_fiber, replace_fiber_fn,
+    use_effect, use_ref, use_state, was_fiber_reset,
+};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+fn counter(_: ()) -> i32 {
+    let (count, set_count) = use_state(|| 0);
+    set_count.update(|prev| prev + 1);
+    count
+}
+
+#[test]
+fn compatible_replacement_keeps_state() {
+    fn counter_v2(_: ()) -> i32 {
+        let (count, set_count) = use_state(|| 0);
+        set_count.update(|prev| prev + 10);
+        count * 100
+    }
+
+    mount_fiber(None, "root", counter).unwrap();
+    call_fiber::<(), i32>("root", ()).unwrap();
+    call_fiber::<(), i32>("root", ()).unwrap();
+
+    replace_fiber_fn("root", counter_v2).unwrap();
+    assert_eq!(call_fiber::<(), i32>("root", ()).unwrap(), 200);
+    assert!(!was_fiber_reset("root").unwrap());
+    assert_eq!(call_fiber::<(), i32>("root", ()).unwrap(), 1200);
+}
+
+#[test]
+fn replacement_may_change_props_and_output() {
+    fn labelled(label: &'static str) -> String {
+        let (count, _) = use_state(|| 0);
+        format!("{label}: {count}")
+    }
+
+    mount_fiber(None, "root", counter).unwrap();
+    call_fiber::<(), i32>("root", ()).unwrap();
+
+    replace_fiber_fn("root", labelled).unwrap();
+    assert_eq!(
+        call_fiber::<&'static str, String>("root", "count").unwrap(),
+        "count: 1"
+    );
+}
+
+#[test]
+fn incompatible_replacement_resets_state() {
+    static CLEANUPS: AtomicU64 = AtomicU64::new(0);
+
+    fn with_effect(_: ()) -> i32 {
+        let (count, set_count) = use_state(|| 0);
+        use_effect(
+            &mut || {
+                || {
+                    CLEANUPS.fetch_add(1, Ordering::Relaxed);
+                }
+            },
+            (),
+        );
+        set_count.update(|prev| prev + 1);
+        count
+    }
+
+    fn with_ref(_: ()) -> i32 {
+        let count = use_ref(-1);
+        let (state, _) = use_state(|| 0);
+        *count.borrow() + state
+    }
+
+    mount_fiber(None, "root", with_effect).unwrap();
+    call_fiber::<(), i32>("root", ()).unwrap();
+    call_fiber::<(), i32>("root", ()).unwrap();
+    // Strict mode already ran the cleanup once while re-mounting the effect.
+    CLEANUPS.store(0, Ordering::Relaxed);
+
+    replace_fiber_fn("root", with_ref).unwrap();
+
+    // The first render still sees the hooks it could reuse, then the state is
+    // torn down and the fiber asks for a fresh render.
+    call_fiber::<(), i32>("root", ()).unwrap();
+    assert_eq!(CLEANUPS.load(Ordering::Relaxed), 1);
+    assert!(is_fiber_dirty("root").unwrap());
+    assert!(was_fiber_reset("root").unwrap());
+
+    assert_eq!(call_fiber::<(), i32>("root", ()).unwrap(), -1);
+    assert!(!is_fiber_dirty("root").unwrap());
+    assert!(!was_fiber_reset("root").unwrap());
+}
+
+#[test]
+fn setters_of_reset_hooks_are_ignored() {
+    fn with_setter(_: ()) -> SetStateAction<i32> {
+        use_state(|| 0).1
+    }
+
+    fn with_ref(_: ()) -> SetStateAction<i32> {
+        let _ = use_ref(7);
+        use_state(|| 0).1
+    }
+
+    mount_fiber(None, "root", with_setter).unwrap();
+    let set_count = call_fiber::<(), SetStateAction<i32>>("root", ()).unwrap();
+
+    replace_fiber_fn("root", with_ref).unwrap();
+    call_fiber::<(), SetStateAction<i32>>("root", ()).unwrap();
+    assert!(was_fiber_reset("root").unwrap());
+    call_fiber::<(), SetStateAction<i32>>("root", ()).unwrap();
+
+    // The setter points at the hooks torn down by the reset.
+    set_count.set(5);
+    assert!(!is_fiber_dirty("root").unwrap());
+}
+
+#[test]
+fn replacing_missing_fiber_fails() {
+    assert!(matches!(
+        replace_fiber_fn("missing", counter),
+        Err(FiberStoreError::FiberDoesntExist(_))
+    ));
+}
@@ -0,0 +1,97 @@
+use hooks_rs::{
+    Context, call_fiber, create_context, inspect, mount_fiber, provide_context,
+    provide_context_debug, use_hook_slot, use_ref, use_ref_debug, use_state, use_state_debug,
+};
+use std::sync::LazyLock;
+
+static ENABLED: LazyLock<Context<bool>> = LazyLock::new(create_context);
+
+fn use_label(label: &'static str) -> &'static str {
+    let mut slot = use_hook_slot(|| label);
+    slot.debug_value();
+    *slot.borrow()
+}
+
+fn parent(_: ()) {
+    let (_, _) = use_state(|| 1u8);
+    provide_context(*ENABLED, true);
+    call_fiber::<(), ()>("child", ()).unwrap();
+}
+
+fn child(_: ()) {
+    let _ = use_ref(Vec::<u32>::new());
+    use_label("hello");
+}
+
+#[test]
+fn inspect_lists_fibers_and_hooks() {
+    mount_fiber(None, "parent", parent).unwrap();
+    mount_fiber(Some("parent".into()), "child", child).unwrap();
+
+    call_fiber::<(), ()>("parent", ()).unwrap();
+    call_fiber::<(), ()>("parent", ()).unwrap();
+    call_fiber::<(), ()>("child", ()).unwrap();
+
+    let inspection = inspect();
+    assert_eq!(inspection.roots.len(), 1);
+
+    let parent = &inspection.roots[0];
+    assert_eq!(parent.id, "parent");
+    assert_eq!(parent.parent, None);
+    assert_eq!(parent.render_count, 2);
+    let kinds: Vec<_> = parent.hooks.iter().map(|h| h.kind.as_str()).collect();
+    assert_eq!(kinds, ["use_state", "provide_context"]);
+    assert_eq!(parent.hooks[0].type_name, "u8");
+
+    let child = &parent.children[0];
+    assert_eq!(child.parent.as_deref(), Some("parent"));
+    assert_eq!(child.render_count, 3);
+    assert_eq!(child.hooks[0].type_name, "alloc::vec::Vec<u32>");
+    assert_eq!(child.hooks[0].value, None);
+    assert_eq!(child.hooks[1].value.as_deref(), Some("\"hello\""));
+}
+
+#[test]
+fn inspection_displays_as_text_tree() {
+    mount_fiber(None, "parent", parent).unwrap();
+    mount_fiber(Some("parent".into()), "child", child).unwrap();
+    call_fiber::<(), ()>("parent", ()).unwrap();
+
+    assert_eq!(
+        inspect().to_string(),
+        "parent (renders: 1)\n\
+        \x20 [0] use_state<u8>\n\
+        \x20 [1] provide_context<bool>\n\
+        \x20 child (renders: 1)\n\
+        \x20   [0] use_ref<alloc::vec::Vec<u32>>\n\
+        \x20   [1] &str<&str> = \"hello\"\n"
+    );
+}
+
+#[test]
+fn debug_variants_show_hook_values() {
+    fn component(_: ()) {
+        let (_, set_count) = use_state_debug(|| 1);
+        set_count.set(2);
+        use_ref_debug(vec!["a"]);
+        provide_context_debug(*ENABLED, false);
+    }
+
+    mount_fiber(None, "root", component).unwrap();
+    call_fiber::<(), ()>("root", ()).unwrap();
+    call_fiber::<(), ()>("root", ()).unwrap();
+
+    let values: Vec<_> = inspect().roots[0]
+        .hooks
+        .iter()
+        .map(|h| h.value.clone())
+        .collect();
+    assert_eq!(
+        values,
+        [
+            Some("2".into()),
+            Some("[\"a\"]".into()),
+            Some("false".into())
+        ]
+    );
+}
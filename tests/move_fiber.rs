@@ -0,0 +1,118 @@
+use std::sync::LazyLock;
+
+use hooks_rs::{
+    Context, FiberStoreError, call_fiber, create_context, get_children_ids, get_parent_id,
+    is_fiber_dirty, mount_fiber, move_fiber, provide_context, use_context, use_state,
+};
+
+static SIDE: LazyLock<Context<&'static str>> = LazyLock::new(create_context);
+
+fn provider(side: &'static str) {
+    provide_context(*SIDE, side);
+}
+
+/// Counts its renders and reads the side it's on.
+fn panel(_: ()) -> (i32, &'static str) {
+    let (renders, set_renders) = use_state(|| 0);
+    set_renders.update(|prev| prev + 1);
+    (renders, use_context(*SIDE))
+}
+
+fn leaf(_: ()) {}
+
+fn render_panel(id: &str) -> (i32, &'static str) {
+    call_fiber::<(), (i32, &'static str)>(id, ()).unwrap()
+}
+
+fn mount_sides() {
+    mount_fiber(None, "left", provider).unwrap();
+    mount_fiber(None, "right", provider).unwrap();
+    call_fiber::<&str, ()>("left", "left").unwrap();
+    call_fiber::<&str, ()>("right", "right").unwrap();
+}
+
+#[test]
+fn moved_subtree_keeps_state_and_reads_new_providers() {
+    mount_sides();
+    mount_fiber(Some("left".into()), "left/panel", panel).unwrap();
+    mount_fiber(Some("left/panel".into()), "left/panel/label", leaf).unwrap();
+
+    assert_eq!(render_panel("left/panel"), (0, "left"));
+    assert_eq!(render_panel("left/panel"), (1, "left"));
+
+    let id = move_fiber("left/panel", Some("right".into()), 0).unwrap();
+    assert_eq!(id, "right/panel");
+    assert!(get_children_ids("left").unwrap().is_empty());
+    assert_eq!(get_children_ids("right").unwrap(), ["right/panel"]);
+    assert_eq!(
+        get_children_ids("right/panel").unwrap(),
+        ["right/panel/label"]
+    );
+    assert_eq!(
+        get_parent_id("right/panel/label").unwrap().as_deref(),
+        Some("right/panel")
+    );
+    assert!(get_parent_id("left/panel/label").is_err());
+
+    assert!(is_fiber_dirty("right/panel").unwrap());
+    assert_eq!(render_panel("right/panel"), (2, "right"));
+}
+
+#[test]
+fn ids_that_are_not_path_based_are_kept() {
+    mount_sides();
+    mount_fiber(Some("left".into()), "panel", panel).unwrap();
+    mount_fiber(Some("panel".into()), "panel/label", leaf).unwrap();
+    mount_fiber(Some("right".into()), "right/first", leaf).unwrap();
+    mount_fiber(Some("right".into()), "right/last", leaf).unwrap();
+
+    assert_eq!(
+        move_fiber("panel", Some("right".into()), 1).unwrap(),
+        "panel"
+    );
+    assert_eq!(
+        get_children_ids("right").unwrap(),
+        ["right/first", "panel", "right/last"]
+    );
+    assert_eq!(get_children_ids("panel").unwrap(), ["panel/label"]);
+}
+
+#[test]
+fn fibers_can_become_roots() {
+    mount_sides();
+    mount_fiber(Some("left".into()), "left/panel", panel).unwrap();
+
+    assert_eq!(move_fiber("left/panel", None, 0).unwrap(), "panel");
+    assert_eq!(get_parent_id("panel").unwrap(), None);
+}
+
+#[test]
+fn invalid_moves_are_rejected() {
+    mount_sides();
+    mount_fiber(Some("left".into()), "left/panel", panel).unwrap();
+    mount_fiber(Some("left/panel".into()), "left/panel/label", leaf).unwrap();
+    mount_fiber(Some("right".into()), "right/panel", leaf).unwrap();
+
+    assert!(matches!(
+        move_fiber("left/panel", Some("left/panel/label".into()), 0),
+        Err(FiberStoreError::MoveIntoDescendant(_))
+    ));
+    assert!(matches!(
+        move_fiber("left/panel", Some("right".into()), 0),
+        Err(FiberStoreError::FiberAlreadyExists(id)) if id == "right/panel"
+    ));
+    assert!(matches!(
+        move_fiber("left/panel", Some("missing".into()), 0),
+        Err(FiberStoreError::ParentDoesNotExist(_))
+    ));
+    assert!(matches!(
+        move_fiber("missing", None, 0),
+        Err(FiberStoreError::FiberDoesntExist(_))
+    ));
+
+    // Nothing changed.
+    assert_eq!(
+        get_children_ids("left/panel").unwrap(),
+        ["left/panel/label"]
+    );
+}
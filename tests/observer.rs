@@ -0,0 +1,156 @@
+use std::{cell::RefCell, rc::Rc};
+
+use hooks_rs::{
+    FiberObserver, RenderedChild, Renderer, VNode, add_observer, call_fiber, create_root,
+    get_parent_id, mount_fiber, remove_observer, unmount_fiber, use_state,
+};
+
+#[derive(Clone, Default)]
+struct Log(Rc<RefCell<Vec<String>>>);
+
+impl Log {
+    fn push(&self, event: String) {
+        self.0.borrow_mut().push(event);
+    }
+
+    fn take(&self) -> Vec<String> {
+        std::mem::take(&mut *self.0.borrow_mut())
+    }
+}
+
+impl FiberObserver for Log {
+    fn on_mount(&self, id: &str) {
+        // Observers may read the tree.
+        let parent = get_parent_id(id).unwrap();
+        self.push(format!("mount {id} under {parent:?}"));
+    }
+
+    fn on_render_start(&self, id: &str) {
+        self.push(format!("render start {id}"));
+    }
+
+    fn on_render_end(&self, id: &str) {
+        self.push(format!("render end {id}"));
+    }
+
+    fn on_commit(&self, id: &str) {
+        self.push(format!("commit {id}"));
+    }
+
+    fn on_state_change(&self, id: &str, hook_index: usize) {
+        self.push(format!("state change {id}[{hook_index}]"));
+    }
+
+    fn on_unmount(&self, id: &str) {
+        self.push(format!("unmount {id}"));
+    }
+}
+
+fn child(_: ()) {}
+
+fn parent(bump: bool) {
+    let (_, set_count) = use_state(|| 0);
+    let (_, set_label) = use_state(String::new);
+    call_fiber::<(), ()>("root/child", ()).unwrap();
+    if bump {
+        set_count.update(|prev| prev + 1);
+        set_label.set("bumped".into());
+    }
+}
+
+#[test]
+fn lifecycle_events_are_observed_in_order() {
+    let log = Log::default();
+    add_observer(log.clone());
+
+    mount_fiber(None, "root", parent).unwrap();
+    mount_fiber(Some("root".into()), "root/child", child).unwrap();
+    assert_eq!(
+        log.take(),
+        [
+            "mount root under None",
+            "mount root/child under Some(\"root\")"
+        ]
+    );
+
+    call_fiber::<bool, ()>("root", true).unwrap();
+    assert_eq!(
+        log.take(),
+        [
+            "render start root",
+            "render start root/child",
+            "render end root/child",
+            "commit root/child",
+            "render end root",
+            "commit root",
+            // Updates made during a render are applied once it's committed.
+            "state change root[0]",
+            "state change root[1]",
+        ]
+    );
+
+    unmount_fiber("root");
+    assert_eq!(log.take(), ["unmount root/child", "unmount root"]);
+}
+
+#[test]
+fn removed_observers_are_not_notified() {
+    let log = Log::default();
+    let id = add_observer(log.clone());
+
+    mount_fiber(None, "root", child).unwrap();
+    assert!(remove_observer(id));
+    assert!(!remove_observer(id));
+
+    call_fiber::<(), ()>("root", ()).unwrap();
+    assert_eq!(log.take(), ["mount root under None"]);
+}
+
+/// Renders nothing, for trees that are only observed.
+struct Nothing;
+
+impl Renderer for Nothing {
+    type Element = ();
+    type Output = ();
+
+    fn element(&mut self, _: (), _: Vec<RenderedChild<()>>) {}
+
+    fn fragment(&mut self, _: Vec<RenderedChild<()>>) {}
+}
+
+#[test]
+fn fibers_mounted_by_the_reconciler_are_observed() {
+    fn leaf(_: ()) -> VNode<()> {
+        VNode::element(())
+    }
+
+    let log = Log::default();
+    add_observer(log.clone());
+
+    let mut root = create_root("r", |show: bool| {
+        VNode::with_children(
+            (),
+            show.then(|| VNode::component("leaf", leaf, ()))
+                .into_iter()
+                .collect(),
+        )
+    });
+    let mounts = |log: &Log| {
+        log.take()
+            .into_iter()
+            .filter(|event| event.starts_with("mount") || event.starts_with("unmount"))
+            .collect::<Vec<_>>()
+    };
+
+    root.render(&mut Nothing, true);
+    assert_eq!(
+        mounts(&log),
+        ["mount r under None", "mount r/leaf under Some(\"r\")"]
+    );
+
+    root.render(&mut Nothing, false);
+    assert_eq!(mounts(&log), ["unmount r/leaf"]);
+
+    root.unmount();
+    assert_eq!(mounts(&log), ["unmount r"]);
+}
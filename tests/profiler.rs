@@ -0,0 +1,122 @@
+use hooks_rs::{
+    Context, Profiler, RenderReason, call_fiber, create_context, mount_fiber, provide_context,
+    provide_context_eq, use_context, use_state,
+};
+use std::sync::LazyLock;
+
+static THEME: LazyLock<Context<&'static str>> = LazyLock::new(create_context);
+
+fn app(theme: &'static str) {
+    provide_context(*THEME, theme);
+    call_fiber::<(), ()>("button", ()).unwrap();
+}
+
+fn app_eq(theme: &'static str) {
+    provide_context_eq(*THEME, theme);
+    call_fiber::<(), ()>("button", ()).unwrap();
+}
+
+fn button(_: ()) {
+    let _ = use_context(*THEME);
+}
+
+fn counter(_: ()) -> i32 {
+    let _ = use_state(|| false);
+    let (count, set_count) = use_state(|| 0);
+    if count == 0 {
+        set_count.set(1);
+    }
+    count
+}
+
+#[test]
+fn records_render_reasons() {
+    mount_fiber(None, "counter", counter).unwrap();
+
+    Profiler::start();
+    call_fiber::<(), i32>("counter", ()).unwrap();
+    call_fiber::<(), i32>("counter", ()).unwrap();
+    call_fiber::<(), i32>("counter", ()).unwrap();
+    let profiler = Profiler::stop().unwrap();
+
+    let reasons: Vec<_> = profiler
+        .records()
+        .iter()
+        .map(|record| record.reasons.clone())
+        .collect();
+    assert_eq!(
+        reasons,
+        [
+            vec![RenderReason::Mount],
+            vec![RenderReason::State { hook_index: 1 }],
+            vec![RenderReason::Props],
+        ]
+    );
+}
+
+#[test]
+fn records_parent_and_context_renders() {
+    mount_fiber(None, "app", app).unwrap();
+    mount_fiber(Some("app".into()), "button", button).unwrap();
+
+    Profiler::start();
+    call_fiber::<&'static str, ()>("app", "light").unwrap();
+    call_fiber::<&'static str, ()>("app", "dark").unwrap();
+    let profiler = Profiler::stop().unwrap();
+
+    let stats = profiler.stats();
+    assert_eq!(stats["app"].render_count, 2);
+    assert_eq!(stats["button"].render_count, 2);
+    assert_eq!(stats["button"].reasons[&RenderReason::Parent], 2);
+    assert_eq!(stats["button"].reasons[&RenderReason::Context], 1);
+    assert!(stats["app"].max_duration >= stats["button"].max_duration);
+
+    // Children finish first, and a parent's render spans its children's.
+    let records = profiler.records();
+    assert_eq!(records[0].fiber_id, "button");
+    assert_eq!(records[1].fiber_id, "app");
+    assert!(records[1].start <= records[0].start);
+}
+
+#[test]
+fn equal_context_values_are_not_context_renders() {
+    mount_fiber(None, "app", app_eq).unwrap();
+    mount_fiber(Some("app".into()), "button", button).unwrap();
+
+    Profiler::start();
+    call_fiber::<&'static str, ()>("app", "light").unwrap();
+    call_fiber::<&'static str, ()>("app", "light").unwrap();
+    call_fiber::<&'static str, ()>("app", "dark").unwrap();
+    let profiler = Profiler::stop().unwrap();
+
+    let stats = profiler.stats();
+    assert_eq!(stats["button"].render_count, 3);
+    assert_eq!(stats["button"].reasons[&RenderReason::Context], 1);
+}
+
+#[test]
+fn exports_chrome_trace_events() {
+    mount_fiber(None, "counter", counter).unwrap();
+
+    Profiler::start();
+    call_fiber::<(), i32>("counter", ()).unwrap();
+    let trace = Profiler::stop().unwrap().to_chrome_trace();
+
+    assert!(
+        trace.starts_with(
+            "{\"traceEvents\":[{\"name\":\"counter\",\"cat\":\"render\",\"ph\":\"X\","
+        )
+    );
+    assert!(trace.ends_with(
+        ",\"pid\":1,\"tid\":1,\"args\":{\"reasons\":[\"mount\"]}}],\"displayTimeUnit\":\"ms\"}"
+    ));
+}
+
+#[test]
+fn nothing_is_recorded_when_stopped() {
+    mount_fiber(None, "counter", counter).unwrap();
+    call_fiber::<(), i32>("counter", ()).unwrap();
+
+    assert!(!Profiler::is_running());
+    assert!(Profiler::stop().is_none());
+}
@@ -0,0 +1,66 @@
+use hooks_rs::{ChildChanges, get_children_ids, mount_fiber, mount_fiber_at, reconcile_children};
+
+fn leaf(_: ()) {}
+
+fn ids(ids: &[&str]) -> Vec<String> {
+    ids.iter().map(|id| id.to_string()).collect()
+}
+
+fn mount_children(children: &[&str]) {
+    mount_fiber(None, "root", leaf).unwrap();
+    for child in children {
+        mount_fiber(Some("root".into()), *child, leaf).unwrap();
+    }
+}
+
+#[test]
+fn inserts_and_removals_are_reported() {
+    mount_children(&["a", "b", "c"]);
+
+    let changes = reconcile_children("root", &ids(&["a", "x", "c"])).unwrap();
+    assert_eq!(
+        changes,
+        ChildChanges {
+            inserted: vec![("x".into(), 1)],
+            removed: vec!["b".into()],
+            moved: vec![],
+        }
+    );
+    assert_eq!(get_children_ids("root").unwrap(), ["a", "c"]);
+    assert!(get_children_ids("b").is_err());
+
+    mount_fiber_at("root", "x", 1, leaf).unwrap();
+    assert_eq!(get_children_ids("root").unwrap(), ["a", "x", "c"]);
+}
+
+#[test]
+fn only_children_out_of_order_are_moved() {
+    mount_children(&["a", "b", "c", "d", "e"]);
+
+    // `b`, `c` and `e` keep their relative order.
+    let changes = reconcile_children("root", &ids(&["d", "b", "c", "a", "e"])).unwrap();
+    assert_eq!(changes.moved, [("d".into(), 0), ("a".into(), 3)]);
+    assert!(changes.inserted.is_empty() && changes.removed.is_empty());
+    assert_eq!(get_children_ids("root").unwrap(), ["d", "b", "c", "a", "e"]);
+
+    let changes = reconcile_children("root", &ids(&["e", "d", "c", "b", "a"])).unwrap();
+    assert_eq!(changes.moved.len(), 2);
+
+    assert!(
+        reconcile_children("root", &ids(&["e", "d", "c", "b", "a"]))
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[test]
+#[should_panic(expected = "Duplicate key `a` among the children of `root`.")]
+fn duplicate_keys_panic() {
+    mount_children(&["a"]);
+    let _ = reconcile_children("root", &ids(&["a", "b", "a"]));
+}
+
+#[test]
+fn missing_parent_is_an_error() {
+    assert!(reconcile_children("root", &[]).is_err());
+}
@@ -0,0 +1,305 @@
+use std::{cell::RefCell, rc::Rc, sync::LazyLock};
+
+use hooks_rs::{
+    Context, RenderedChild, Renderer, VNode, create_context, create_root, get_children_ids,
+    get_parent_id, provide_context, use_context, use_effect, use_state,
+};
+
+/// Renders elements as `tag[key](children)`.
+struct Markup;
+
+impl Renderer for Markup {
+    type Element = &'static str;
+    type Output = String;
+
+    fn element(&mut self, tag: &'static str, children: Vec<RenderedChild<String>>) -> String {
+        let mut out = tag.to_string();
+        if !children.is_empty() {
+            out += &format!("({})", join(children));
+        }
+        out
+    }
+
+    fn fragment(&mut self, children: Vec<RenderedChild<String>>) -> String {
+        join(children)
+    }
+}
+
+fn join(children: Vec<RenderedChild<String>>) -> String {
+    children
+        .into_iter()
+        .map(|c| match c.key {
+            Some(key) => format!("{}[{key}]", c.output),
+            None => c.output,
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn counter(label: &'static str) -> VNode<&'static str> {
+    let (count, set_count) = use_state(|| 0);
+    set_count.update(|prev| prev + 1);
+    VNode::element(if count == 0 { label } else { "updated" })
+}
+
+#[test]
+fn components_keep_state_across_renders() {
+    fn app(_: ()) -> VNode<&'static str> {
+        VNode::with_children(
+            "app",
+            vec![
+                VNode::component("a", counter, "a"),
+                VNode::element("text"),
+                VNode::component("b", counter, "b"),
+            ],
+        )
+    }
+
+    let mut root = create_root("root", app);
+    assert_eq!(root.render(&mut Markup, ()), "app(a text b)");
+    assert_eq!(root.render(&mut Markup, ()), "app(updated text updated)");
+    assert_eq!(get_children_ids("root").unwrap(), ["root/a", "root/b"]);
+}
+
+#[test]
+fn fragments_and_keyed_lists_are_flattened() {
+    fn app(items: Vec<&'static str>) -> VNode<&'static str> {
+        VNode::fragment(vec![
+            VNode::element("header"),
+            VNode::with_children(
+                "list",
+                vec![VNode::keyed(
+                    items
+                        .into_iter()
+                        .map(|item| (item, VNode::component(item, counter, item))),
+                )],
+            ),
+        ])
+    }
+
+    let mut root = create_root("root", app);
+    assert_eq!(
+        root.render(&mut Markup, vec!["x", "y"]),
+        "header list(x[x] y[y])"
+    );
+}
+
+#[test]
+fn removed_components_are_unmounted() {
+    let cleanups = Rc::new(RefCell::new(Vec::new()));
+
+    let tracked = {
+        let cleanups = cleanups.clone();
+        move |label: &'static str| -> VNode<&'static str> {
+            let cleanups = cleanups.clone();
+            use_effect(
+                &mut move || {
+                    let cleanups = cleanups.clone();
+                    move || cleanups.borrow_mut().push(label)
+                },
+                (),
+            );
+            VNode::element(label)
+        }
+    };
+
+    let app = move |show: bool| -> VNode<&'static str> {
+        let mut children = vec![VNode::component("kept", tracked.clone(), "kept")];
+        if show {
+            children.push(VNode::component("shown", tracked.clone(), "shown"));
+        }
+        VNode::with_children("app", children)
+    };
+
+    let mut root = create_root("root", app);
+    assert_eq!(root.render(&mut Markup, true), "app(kept shown)");
+    // Strict mode already ran each cleanup once while re-mounting the effects.
+    cleanups.borrow_mut().clear();
+    assert_eq!(root.render(&mut Markup, false), "app(kept)");
+    assert_eq!(*cleanups.borrow(), ["shown"]);
+    assert_eq!(get_children_ids("root").unwrap(), ["root/kept"]);
+
+    root.unmount();
+    assert_eq!(*cleanups.borrow(), ["shown", "kept"]);
+    assert!(get_children_ids("root").is_err());
+}
+
+#[test]
+fn a_component_of_another_type_is_remounted() {
+    fn number(_: i32) -> VNode<&'static str> {
+        VNode::element("number")
+    }
+
+    fn app(number_child: bool) -> VNode<&'static str> {
+        if number_child {
+            VNode::component("child", number, 1)
+        } else {
+            VNode::component("child", counter, "label")
+        }
+    }
+
+    let mut root = create_root("root", app);
+    assert_eq!(root.render(&mut Markup, false), "label");
+    assert_eq!(root.render(&mut Markup, true), "number");
+    assert_eq!(root.render(&mut Markup, false), "label");
+}
+
+#[test]
+fn another_component_with_the_same_props_is_remounted() {
+    fn login(_: ()) -> VNode<&'static str> {
+        VNode::element("login")
+    }
+
+    fn home(_: ()) -> VNode<&'static str> {
+        VNode::element("home")
+    }
+
+    fn app(logged_in: bool) -> VNode<&'static str> {
+        if logged_in {
+            VNode::component("body", home, ())
+        } else {
+            VNode::component("body", login, ())
+        }
+    }
+
+    let mut root = create_root("root", app);
+    assert_eq!(root.render(&mut Markup, false), "login");
+    assert_eq!(root.render(&mut Markup, true), "home");
+}
+
+#[test]
+fn closures_render_their_latest_captures() {
+    fn app(label: &'static str) -> VNode<&'static str> {
+        VNode::component("label", move |_: ()| VNode::element(label), ())
+    }
+
+    let mut root = create_root("root", app);
+    assert_eq!(root.render(&mut Markup, "first"), "first");
+    assert_eq!(root.render(&mut Markup, "second"), "second");
+}
+
+#[test]
+fn reordered_keyed_components_keep_their_state() {
+    fn item(label: &'static str) -> VNode<&'static str> {
+        let (renders, set_renders) = use_state(|| 0);
+        set_renders.update(|prev| prev + 1);
+        VNode::element(if renders == 0 { label } else { "kept" })
+    }
+
+    fn list(items: Vec<&'static str>) -> VNode<&'static str> {
+        VNode::keyed(
+            items
+                .into_iter()
+                .map(|key| (key, VNode::component(key, item, key))),
+        )
+    }
+
+    let mut root = create_root("root", list);
+    assert_eq!(root.render(&mut Markup, vec!["a", "b"]), "a[a] b[b]");
+    assert_eq!(
+        root.render(&mut Markup, vec!["c", "b", "a"]),
+        "c[c] kept[b] kept[a]"
+    );
+    assert_eq!(
+        get_children_ids("root").unwrap(),
+        ["root/c:c", "root/b:b", "root/a:a"]
+    );
+}
+
+#[test]
+fn state_follows_keyed_items_with_the_same_component_key() {
+    fn row(label: &'static str) -> VNode<&'static str> {
+        let (first, _) = use_state(|| label);
+        VNode::element(first)
+    }
+
+    fn list(items: Vec<&'static str>) -> VNode<&'static str> {
+        VNode::keyed(items.into_iter().map(|key| {
+            (
+                key,
+                VNode::with_children("li", vec![VNode::component("row", row, key)]),
+            )
+        }))
+    }
+
+    let mut root = create_root("root", list);
+    assert_eq!(
+        root.render(&mut Markup, vec!["a", "b"]),
+        "li(a)[a] li(b)[b]"
+    );
+    assert_eq!(
+        get_children_ids("root").unwrap(),
+        ["root/a:row", "root/b:row"]
+    );
+
+    // Each row keeps the state it was mounted with.
+    assert_eq!(
+        root.render(&mut Markup, vec!["b", "c", "a"]),
+        "li(b)[b] li(c)[c] li(a)[a]"
+    );
+    assert_eq!(
+        get_children_ids("root").unwrap(),
+        ["root/b:row", "root/c:row", "root/a:row"]
+    );
+}
+
+#[test]
+#[should_panic(expected = "Duplicate key `root/a` among the children of `root`.")]
+fn duplicate_component_keys_panic() {
+    fn app(_: ()) -> VNode<&'static str> {
+        VNode::fragment(vec![
+            VNode::component("a", counter, "a"),
+            VNode::component("a", counter, "b"),
+        ])
+    }
+
+    create_root("root", app).render(&mut Markup, ());
+}
+
+#[test]
+#[should_panic(expected = "Duplicate key `a` among the keyed items of `root`.")]
+fn duplicate_item_keys_panic() {
+    fn app(_: ()) -> VNode<&'static str> {
+        VNode::keyed([
+            ("a", VNode::element("first")),
+            ("a", VNode::element("second")),
+        ])
+    }
+
+    create_root("root", app).render(&mut Markup, ());
+}
+
+#[test]
+fn portals_render_elsewhere_but_keep_their_parent() {
+    static THEME: LazyLock<Context<&'static str>> = LazyLock::new(create_context);
+
+    fn modal(_: ()) -> VNode<&'static str> {
+        VNode::element(use_context(*THEME))
+    }
+
+    fn page(_: ()) -> VNode<&'static str> {
+        provide_context(*THEME, "dark");
+        VNode::with_children(
+            "page",
+            vec![
+                VNode::element("content"),
+                VNode::portal("overlay", vec![VNode::component("modal", modal, ())]),
+            ],
+        )
+    }
+
+    fn app(_: ()) -> VNode<&'static str> {
+        VNode::component("page", page, ())
+    }
+
+    let mut root = create_root("root", app);
+    let rendered = root.render_with_portals(&mut Markup, ());
+    assert_eq!(rendered.output, "page(content)");
+    assert_eq!(rendered.portals["overlay"], "dark");
+    assert_eq!(
+        get_parent_id("root/page/modal").unwrap().as_deref(),
+        Some("root/page")
+    );
+
+    assert_eq!(root.render(&mut Markup, ()), "page(content)");
+}
@@ -0,0 +1,72 @@
+use std::thread;
+
+use hooks_rs::{
+    SetStateAction, call_fiber, is_fiber_dirty, mount_fiber, move_fiber, process_remote_updates,
+    unmount_fiber, use_state,
+};
+
+type Counter = (i32, SetStateAction<i32>);
+
+fn counter(_: ()) -> Counter {
+    use_state(|| 0)
+}
+
+fn render(id: &str) -> Counter {
+    call_fiber::<(), Counter>(id, ()).unwrap()
+}
+
+#[test]
+fn remote_updates_apply_when_processed() {
+    mount_fiber(None, "root", counter).unwrap();
+    let remote = render("root").1.remote();
+
+    let workers: Vec<_> = (0..3)
+        .map(|_| {
+            let remote = remote.clone();
+            thread::spawn(move || remote.update(|prev| prev + 1))
+        })
+        .collect();
+    for worker in workers {
+        worker.join().unwrap();
+    }
+
+    assert!(remote.has_pending_updates());
+    assert!(!is_fiber_dirty("root").unwrap());
+
+    assert_eq!(process_remote_updates(), 3);
+    assert!(!remote.has_pending_updates());
+    assert!(is_fiber_dirty("root").unwrap());
+    assert_eq!(render("root").0, 3);
+
+    assert_eq!(process_remote_updates(), 0);
+}
+
+#[test]
+fn updates_for_unmounted_fibers_are_dropped() {
+    mount_fiber(None, "root", counter).unwrap();
+    let remote = render("root").1.remote();
+    thread::spawn(move || remote.set(1)).join().unwrap();
+
+    // Same id, but another mount: it must not receive the update.
+    unmount_fiber("root");
+    mount_fiber(None, "root", counter).unwrap();
+    render("root");
+
+    assert_eq!(process_remote_updates(), 1);
+    assert!(!is_fiber_dirty("root").unwrap());
+    assert_eq!(render("root").0, 0);
+}
+
+#[test]
+fn moved_fibers_still_receive_updates() {
+    mount_fiber(None, "left", |_: ()| {}).unwrap();
+    mount_fiber(None, "right", |_: ()| {}).unwrap();
+    mount_fiber(Some("left".into()), "left/counter", counter).unwrap();
+    let remote = render("left/counter").1.remote();
+
+    let id = move_fiber("left/counter", Some("right".into()), 0).unwrap();
+    thread::spawn(move || remote.set(7)).join().unwrap();
+
+    process_remote_updates();
+    assert_eq!(render(&id).0, 7);
+}
@@ -0,0 +1,164 @@
+#![cfg(feature = "serde")]
+
+use hooks_rs::{
+    Snapshot, call_fiber, mount_fiber, restore, snapshot, unmount_fiber, use_ref, use_state,
+    use_state_serde,
+};
+
+fn editor(_: ()) -> (String, bool) {
+    let (draft, set_draft) = use_state_serde(String::new);
+    let (bold, set_bold) = use_state_serde(|| false);
+    let _ = use_ref(0u8);
+    let _ = use_state(|| 0u32);
+
+    if draft.is_empty() {
+        set_draft.set("hello".to_string());
+        set_bold.set(true);
+    }
+    (draft, bold)
+}
+
+#[test]
+fn snapshot_round_trips_through_restore() {
+    mount_fiber(None, "editor", editor).unwrap();
+    call_fiber::<(), (String, bool)>("editor", ()).unwrap();
+    assert_eq!(
+        call_fiber::<(), (String, bool)>("editor", ()).unwrap(),
+        ("hello".to_string(), true)
+    );
+
+    let json = serde_json::to_string(&snapshot()).unwrap();
+    unmount_fiber("editor");
+
+    let saved: Snapshot = serde_json::from_str(&json).unwrap();
+    mount_fiber(None, "editor", editor).unwrap();
+    assert!(restore(&saved).is_empty());
+
+    // The first render starts from the restored values.
+    assert_eq!(
+        call_fiber::<(), (String, bool)>("editor", ()).unwrap(),
+        ("hello".to_string(), true)
+    );
+}
+
+#[test]
+fn snapshot_reports_skipped_slots() {
+    mount_fiber(None, "editor", editor).unwrap();
+    call_fiber::<(), (String, bool)>("editor", ()).unwrap();
+
+    let snapshot = snapshot();
+    let slots = &snapshot.fibers["editor"];
+    assert_eq!(slots.len(), 2);
+    assert_eq!(slots[&0], serde_json::json!(""));
+    assert_eq!(slots[&1], serde_json::json!(false));
+
+    let skipped: Vec<_> = snapshot
+        .skipped
+        .iter()
+        .map(|slot| (slot.hook_index, slot.kind.as_str()))
+        .collect();
+    assert_eq!(skipped, [(2, "use_ref"), (3, "use_state")]);
+}
+
+#[test]
+fn restore_skips_missing_and_rendered_fibers() {
+    mount_fiber(None, "editor", editor).unwrap();
+    call_fiber::<(), (String, bool)>("editor", ()).unwrap();
+
+    let mut saved = snapshot();
+    saved
+        .fibers
+        .insert("missing".to_string(), [(0, serde_json::json!(1))].into());
+
+    let mut skipped: Vec<_> = restore(&saved)
+        .skipped()
+        .into_iter()
+        .map(|slot| (slot.fiber_id, slot.hook_index, slot.reason))
+        .collect();
+    skipped.sort();
+
+    assert_eq!(
+        skipped,
+        [
+            ("editor".into(), 0, "fiber has already rendered".into()),
+            ("editor".into(), 1, "fiber has already rendered".into()),
+            ("missing".into(), 0, "fiber doesn't exist".into()),
+        ]
+    );
+}
+
+#[test]
+fn mismatched_restored_value_falls_back_to_initial() {
+    let mut saved = Snapshot::default();
+    saved.fibers.insert(
+        "editor".to_string(),
+        [(1, serde_json::json!("not a bool"))].into(),
+    );
+
+    mount_fiber(None, "editor", editor).unwrap();
+    let report = restore(&saved);
+    assert!(report.is_empty());
+
+    assert_eq!(
+        call_fiber::<(), (String, bool)>("editor", ()).unwrap(),
+        (String::new(), false)
+    );
+
+    // The value is checked when the hook mounts.
+    let skipped = report.skipped();
+    assert_eq!(skipped.len(), 1);
+    assert_eq!(skipped[0].fiber_id, "editor");
+    assert_eq!(skipped[0].hook_index, 1);
+    assert_eq!(skipped[0].kind, "use_state");
+    assert!(
+        skipped[0]
+            .reason
+            .starts_with("couldn't deserialize the value: invalid type: string \"not a bool\""),
+        "{}",
+        skipped[0].reason
+    );
+}
+
+#[test]
+fn values_no_hook_takes_are_reported() {
+    let mut saved = Snapshot::default();
+    saved.fibers.insert(
+        "editor".to_string(),
+        [
+            (0, serde_json::json!("draft")),
+            (3, serde_json::json!(5)),
+            (7, serde_json::json!(true)),
+        ]
+        .into(),
+    );
+
+    mount_fiber(None, "editor", editor).unwrap();
+    let report = restore(&saved);
+    assert!(report.is_empty());
+
+    assert_eq!(
+        call_fiber::<(), (String, bool)>("editor", ()).unwrap(),
+        ("draft".to_string(), false)
+    );
+
+    let skipped: Vec<_> = report
+        .skipped()
+        .into_iter()
+        .map(|s| (s.hook_index, s.kind, s.reason))
+        .collect();
+    assert_eq!(
+        skipped,
+        [
+            (
+                3,
+                "use_state".to_string(),
+                "hook doesn't restore values".to_string()
+            ),
+            (7, String::new(), "no hook at this slot".to_string()),
+        ]
+    );
+
+    // Later renders don't report them again.
+    call_fiber::<(), (String, bool)>("editor", ()).unwrap();
+    assert_eq!(report.skipped().len(), 2);
+}
@@ -0,0 +1,49 @@
+#![cfg(feature = "strict")]
+
+use std::{
+    cell::RefCell,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use hooks_rs::{call_fiber, mount_fiber, use_effect};
+
+#[test]
+fn fibers_render_once_per_call() {
+    static RENDERS: AtomicU64 = AtomicU64::new(0);
+
+    fn component(_: ()) {
+        RENDERS.fetch_add(1, Ordering::Relaxed);
+    }
+
+    mount_fiber(None, "root", component).unwrap();
+
+    // Only effects are re-mounted, renders aren't doubled.
+    call_fiber::<(), ()>("root", ()).unwrap();
+    call_fiber::<(), ()>("root", ()).unwrap();
+    assert_eq!(RENDERS.load(Ordering::Relaxed), 2);
+}
+
+#[test]
+fn effects_are_remounted_on_mount() {
+    thread_local! {
+        static LOG: RefCell<Vec<&'static str>> = const { RefCell::new(Vec::new()) };
+    }
+
+    fn component(dep: i32) {
+        use_effect(
+            &mut || {
+                LOG.with(|l| l.borrow_mut().push("mount"));
+                || LOG.with(|l| l.borrow_mut().push("cleanup"))
+            },
+            (dep,),
+        );
+    }
+
+    mount_fiber(None, "root", component).unwrap();
+
+    call_fiber::<i32, ()>("root", 1).unwrap();
+    LOG.with(|l| assert_eq!(*l.borrow(), ["mount", "cleanup", "mount"]));
+
+    call_fiber::<i32, ()>("root", 1).unwrap();
+    LOG.with(|l| assert_eq!(l.borrow().len(), 3));
+}
@@ -0,0 +1,146 @@
+#![cfg(feature = "sync")]
+
+use std::{
+    rc::Rc,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+    thread,
+};
+
+use hooks_rs::{
+    Context, FiberStoreError,
+    sync::{SyncRuntime, SyncSetter, use_ref, use_state},
+    use_effect,
+};
+
+type Output = (i32, SyncSetter<i32>);
+
+fn counter(_: ()) -> Output {
+    use_state(|| 0)
+}
+
+type Shared = Arc<Mutex<Vec<i32>>>;
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+fn runtime_setters_and_contexts_can_be_shared() {
+    assert_send_sync::<SyncRuntime>();
+    assert_send_sync::<SyncSetter<i32>>();
+    assert_send_sync::<Context<Rc<i32>>>();
+}
+
+#[test]
+fn fibers_can_be_driven_from_other_threads() {
+    let runtime = SyncRuntime::new();
+    runtime.mount_fiber(None, "root", counter).unwrap();
+
+    let workers: Vec<_> = (0..4)
+        .map(|_| {
+            let runtime = runtime.clone();
+            thread::spawn(move || {
+                let (_, set_count) = runtime.call_fiber::<(), Output>("root", ()).unwrap();
+                set_count.update(|prev| prev + 1);
+            })
+        })
+        .collect();
+    for worker in workers {
+        worker.join().unwrap();
+    }
+
+    assert!(runtime.has_pending_updates());
+    assert_eq!(runtime.process_updates(), 4);
+    assert!(!runtime.has_pending_updates());
+    assert_eq!(runtime.call_fiber::<(), Output>("root", ()).unwrap().0, 4);
+}
+
+#[test]
+fn fibers_render_on_the_render_thread() {
+    fn component(_: ()) -> thread::ThreadId {
+        thread::current().id()
+    }
+
+    let runtime = SyncRuntime::new();
+    runtime.mount_fiber(None, "root", component).unwrap();
+
+    let render_thread = runtime
+        .call_fiber::<(), thread::ThreadId>("root", ())
+        .unwrap();
+    assert_ne!(render_thread, thread::current().id());
+    assert_eq!(runtime.run(|| thread::current().id()), render_thread);
+}
+
+#[test]
+fn runtimes_have_trees_of_their_own() {
+    let first = SyncRuntime::new();
+    let second = SyncRuntime::new();
+    first.mount_fiber(None, "root", counter).unwrap();
+
+    assert!(matches!(
+        second.call_fiber::<(), Output>("root", ()),
+        Err(FiberStoreError::FiberDoesntExist(_))
+    ));
+    second.mount_fiber(None, "root", counter).unwrap();
+}
+
+#[test]
+fn updates_to_unmounted_fibers_are_dropped() {
+    let runtime = SyncRuntime::new();
+    runtime.mount_fiber(None, "root", counter).unwrap();
+    let (_, set_count) = runtime.call_fiber::<(), Output>("root", ()).unwrap();
+
+    thread::spawn(move || set_count.set(1)).join().unwrap();
+    runtime.unmount_fiber("root");
+
+    assert_eq!(runtime.process_updates(), 1);
+}
+
+#[test]
+fn refs_are_shared_with_other_threads() {
+    fn component(_: ()) -> Shared {
+        use_ref(Vec::new())
+    }
+
+    let runtime = SyncRuntime::new();
+    runtime.mount_fiber(None, "root", component).unwrap();
+    let values = runtime.call_fiber::<(), Shared>("root", ()).unwrap();
+
+    let worker = values.clone();
+    thread::spawn(move || worker.lock().unwrap().push(1))
+        .join()
+        .unwrap();
+
+    let values_again = runtime.call_fiber::<(), Shared>("root", ()).unwrap();
+    assert_eq!(*values_again.lock().unwrap(), [1]);
+}
+
+#[test]
+#[should_panic(expected = "render failed")]
+fn panics_during_a_render_reach_the_caller() {
+    fn component(_: ()) {
+        panic!("render failed");
+    }
+
+    let runtime = SyncRuntime::new();
+    runtime.mount_fiber(None, "root", component).unwrap();
+    let _ = runtime.call_fiber::<(), ()>("root", ());
+}
+
+#[test]
+fn dropping_the_runtime_unmounts_its_fibers() {
+    static CLEANUPS: AtomicU64 = AtomicU64::new(0);
+
+    fn component(_: ()) {
+        use_effect(&mut || || _ = CLEANUPS.fetch_add(1, Ordering::Relaxed), ());
+    }
+
+    let runtime = SyncRuntime::new();
+    runtime.mount_fiber(None, "root", component).unwrap();
+    runtime.call_fiber::<(), ()>("root", ()).unwrap();
+
+    let mount_cleanups = CLEANUPS.load(Ordering::Relaxed);
+    drop(runtime);
+    assert_eq!(CLEANUPS.load(Ordering::Relaxed), mount_cleanups + 1);
+}
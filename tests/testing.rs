@@ -0,0 +1,110 @@
+use hooks_rs::{
+    SetStateAction, call_fiber, mount_fiber, testing::TestHarness, use_effect, use_state,
+};
+use std::cell::Cell;
+
+thread_local! {
+    static SET_COUNT: Cell<Option<SetStateAction<i32>>> = const { Cell::new(None) };
+    static CLEANUPS: Cell<u32> = const { Cell::new(0) };
+}
+
+// Strict mode runs mount effects twice.
+const MOUNT_EFFECTS: u64 = if cfg!(feature = "strict") { 2 } else { 1 };
+
+fn counter(label: &'static str) -> String {
+    let (count, set_count) = use_state(|| 0);
+    SET_COUNT.with(|s| s.set(Some(set_count)));
+
+    use_effect(&mut || || CLEANUPS.with(|c| c.set(c.get() + 1)), (count,));
+
+    format!("{label}: {count}")
+}
+
+fn increment() {
+    SET_COUNT.with(|s| s.get()).unwrap().update(|prev| prev + 1);
+}
+
+#[test]
+fn render_and_rerender() {
+    let mut harness = TestHarness::new(counter);
+
+    assert_eq!(harness.render("count"), "count: 0");
+    assert_eq!(harness.rerender(), "count: 0");
+    harness.assert_render_count(2);
+    harness.assert_effect_count(MOUNT_EFFECTS);
+}
+
+#[test]
+fn act_flushes_updates_and_effects() {
+    let mut harness = TestHarness::new(counter);
+    harness.render("count");
+
+    harness.act(|| {
+        increment();
+        increment();
+    });
+
+    assert!(!harness.is_dirty());
+    harness.assert_render_count(2);
+    harness.assert_effect_count(MOUNT_EFFECTS + 1);
+    assert_eq!(harness.rerender(), "count: 2");
+}
+
+#[test]
+fn unmount_runs_cleanups() {
+    let mut harness = TestHarness::new(counter);
+    harness.render("count");
+    harness.act(increment);
+
+    CLEANUPS.with(|c| c.set(0));
+    harness.unmount();
+    assert_eq!(CLEANUPS.with(Cell::get), 1);
+}
+
+#[test]
+fn dropping_unmounts_and_restores_the_tree() {
+    mount_fiber(None, "outside", counter).unwrap();
+
+    let mut harness = TestHarness::new(counter);
+    harness.render("count");
+    CLEANUPS.with(|c| c.set(0));
+    drop(harness);
+
+    assert_eq!(CLEANUPS.with(Cell::get), 1);
+    // The thread's tree is the one from before the harness.
+    assert_eq!(
+        call_fiber::<&'static str, String>("outside", "outside").unwrap(),
+        "outside: 0"
+    );
+    assert!(call_fiber::<&'static str, String>("root", "root").is_err());
+}
+
+#[test]
+fn harnesses_have_isolated_trees() {
+    fn parent(_: ()) -> i32 {
+        mount_fiber(Some("root".into()), "child", counter).ok();
+        call_fiber::<&'static str, String>("child", "child").unwrap();
+        1
+    }
+
+    let mut first = TestHarness::new(parent);
+    let mut second = TestHarness::new(parent);
+    first.render(());
+    second.render(());
+    first.rerender();
+
+    // Children land in the tree of the harness that rendered them.
+    first.assert_effect_count(MOUNT_EFFECTS);
+    second.assert_effect_count(MOUNT_EFFECTS);
+
+    // The global tree is left alone.
+    mount_fiber(None, "root", counter).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Expected the component to render 3 time(s).")]
+fn render_count_assertion_fails() {
+    let mut harness = TestHarness::new(counter);
+    harness.render("count");
+    harness.assert_render_count(3);
+}
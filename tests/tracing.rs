@@ -0,0 +1,115 @@
+#![cfg(feature = "tracing")]
+
+use hooks_rs::{call_fiber, mount_fiber, unmount_fiber, use_effect, use_state};
+use std::{
+    fmt::Debug,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+use tracing::{
+    Event, Metadata, Subscriber,
+    field::{Field, Visit},
+    span::{Attributes, Id, Record},
+};
+
+/// Records spans and events as `name field=value ...` lines.
+#[derive(Clone, Default)]
+struct Recorder {
+    lines: Arc<Mutex<Vec<String>>>,
+    next_id: Arc<AtomicU64>,
+}
+
+struct Fields(String);
+
+impl Visit for Fields {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        if field.name() == "message" {
+            self.0 = format!("{value:?}{}", self.0);
+        } else {
+            self.0 += &format!(" {}={value:?}", field.name());
+        }
+    }
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let mut fields = Fields(span.metadata().name().to_string());
+        span.record(&mut fields);
+        self.lines.lock().unwrap().push(fields.0);
+        Id::from_u64(self.next_id.fetch_add(1, Ordering::Relaxed) + 1)
+    }
+
+    fn record(&self, _: &Id, _: &Record<'_>) {}
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = Fields(String::new());
+        event.record(&mut fields);
+        self.lines.lock().unwrap().push(fields.0);
+    }
+
+    fn enter(&self, _: &Id) {}
+
+    fn exit(&self, _: &Id) {}
+}
+
+#[test]
+fn renders_hooks_and_setters_are_traced() {
+    fn component(dep: i32) {
+        let (_, set_count) = use_state(|| 0);
+        use_effect(&mut || {}, (0, dep));
+        if dep == 1 {
+            set_count.set(1);
+        }
+    }
+
+    let recorder = Recorder::default();
+    tracing::subscriber::with_default(recorder.clone(), || {
+        mount_fiber(None, "root", component).unwrap();
+        call_fiber::<i32, ()>("root", 0).unwrap();
+        call_fiber::<i32, ()>("root", 1).unwrap();
+        unmount_fiber("root");
+    });
+
+    let lines = recorder.lines.lock().unwrap();
+    assert_eq!(
+        *lines,
+        [
+            "mount fiber=root parent=None",
+            "call_fiber fiber=root props=\"i32\"",
+            "hook fiber=root index=0 kind=\"use_state\" mounted=true",
+            "hook fiber=root index=1 kind=\"use_effect\" mounted=true",
+            "effect mount index=1",
+            "call_fiber fiber=root props=\"i32\"",
+            "hook fiber=root index=0 kind=\"use_state\" mounted=false",
+            "hook fiber=root index=1 kind=\"use_effect\" mounted=false",
+            "effect update index=1 changed_dep=Some(1)",
+            "set state fiber=root index=0 state=\"i32\"",
+            "unmount fiber=root",
+        ]
+    );
+}
+
+#[test]
+fn failed_mounts_are_not_traced() {
+    fn component(_: ()) {}
+
+    let recorder = Recorder::default();
+    tracing::subscriber::with_default(recorder.clone(), || {
+        mount_fiber(None, "root", component).unwrap();
+        assert!(mount_fiber(None, "root", component).is_err());
+        assert!(mount_fiber(Some("missing".into()), "child", component).is_err());
+    });
+
+    assert_eq!(
+        *recorder.lines.lock().unwrap(),
+        ["mount fiber=root parent=None"]
+    );
+}
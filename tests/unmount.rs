@@ -0,0 +1,101 @@
+use std::cell::RefCell;
+
+use hooks_rs::{call_fiber, mount_fiber, unmount_fiber, use_effect, use_hook_slot, use_state};
+
+thread_local! {
+    static LOG: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+fn log(event: String) {
+    LOG.with(|l| l.borrow_mut().push(event));
+}
+
+fn take_log() -> Vec<String> {
+    LOG.with(|l| std::mem::take(&mut *l.borrow_mut()))
+}
+
+fn use_logged_effect(name: String) {
+    use_effect(
+        &mut || {
+            let name = name.clone();
+            move || log(format!("cleanup {name}"))
+        },
+        (),
+    );
+}
+
+/// Logs when the slot holding it is dropped.
+struct Resource(String);
+
+impl Drop for Resource {
+    fn drop(&mut self) {
+        log(format!("drop {}", self.0));
+    }
+}
+
+fn child(name: &'static str) {
+    use_logged_effect(format!("{name}[0]"));
+    use_logged_effect(format!("{name}[1]"));
+}
+
+fn parent(_: ()) {
+    use_logged_effect("parent[0]".into());
+    let mut slot = use_hook_slot(|| Resource("parent[1]".into()));
+    slot.on_unmount(|resource| log(format!("unmount {}", resource.0)));
+    use_logged_effect("parent[2]".into());
+
+    call_fiber::<&str, ()>("root/a", "a").unwrap();
+    call_fiber::<&str, ()>("root/b", "b").unwrap();
+}
+
+#[test]
+fn children_are_torn_down_before_parents_and_hooks_in_reverse() {
+    mount_fiber(None, "root", parent).unwrap();
+    mount_fiber(Some("root".into()), "root/a", child).unwrap();
+    mount_fiber(Some("root".into()), "root/b", child).unwrap();
+    mount_fiber(Some("root/a".into()), "root/a/leaf", child).unwrap();
+    call_fiber::<&str, ()>("root/a/leaf", "leaf").unwrap();
+    call_fiber::<(), ()>("root", ()).unwrap();
+    // Strict mode re-mounts the effects, which logs their cleanups once.
+    take_log();
+
+    unmount_fiber("root");
+    assert_eq!(
+        take_log(),
+        [
+            "cleanup leaf[1]",
+            "cleanup leaf[0]",
+            "cleanup a[1]",
+            "cleanup a[0]",
+            "cleanup b[1]",
+            "cleanup b[0]",
+            "cleanup parent[2]",
+            "unmount parent[1]",
+            "drop parent[1]",
+            "cleanup parent[0]",
+        ]
+    );
+}
+
+#[test]
+fn earlier_hooks_are_available_to_later_cleanups() {
+    fn component(_: ()) {
+        let (count, set_count) = use_state(|| 1);
+        use_effect(
+            &mut || {
+                move || {
+                    set_count.update(|prev| prev + 1);
+                    log(format!("cleanup saw {count}"));
+                }
+            },
+            (),
+        );
+    }
+
+    mount_fiber(None, "root", component).unwrap();
+    call_fiber::<(), ()>("root", ()).unwrap();
+    take_log();
+    unmount_fiber("root");
+
+    assert_eq!(take_log(), ["cleanup saw 1"]);
+}
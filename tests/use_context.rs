@@ -4,7 +4,7 @@ use hooks_rs::{
     Context, FiberStoreError, call_fiber, create_context, mount_fiber, provide_context, use_context,
 };
 
-static CTX: LazyLock<Context<i32>> = LazyLock::new(|| create_context());
+static CTX: LazyLock<Context<i32>> = LazyLock::new(create_context);
 
 fn parent(_: ()) -> i32 {
     provide_context(*CTX, 5);
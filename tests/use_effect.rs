@@ -1,6 +1,9 @@
-use hooks_rs::{DynEq, call_fiber, mount_fiber, use_effect};
+use hooks_rs::{DynEq, call_fiber, deps, mount_fiber, use_effect};
 use std::sync::atomic::{AtomicU64, Ordering};
 
+/// Strict mode runs every effect a second time when it mounts.
+const MOUNT_RUNS: u64 = if cfg!(feature = "strict") { 2 } else { 1 };
+
 #[test]
 fn should_work_single() {
     static CALLS: AtomicU64 = AtomicU64::new(0);
@@ -10,28 +13,28 @@ fn should_work_single() {
             &mut || {
                 CALLS.fetch_add(1, Ordering::Relaxed);
             },
-            vec![Box::new(dep)],
+            deps![dep],
         );
     }
 
-    mount_fiber(None, "root", |dep| component(dep)).unwrap();
+    mount_fiber(None, "root", component).unwrap();
 
     // Should run on mount
     let component = |deps| call_fiber::<i32, ()>("root", deps).unwrap();
 
     component(1);
-    assert_eq!(CALLS.load(Ordering::Relaxed), 1);
+    assert_eq!(CALLS.load(Ordering::Relaxed), MOUNT_RUNS);
 
     // Shouldn't run if deps don t change
     component(1);
-    assert_eq!(CALLS.load(Ordering::Relaxed), 1);
+    assert_eq!(CALLS.load(Ordering::Relaxed), MOUNT_RUNS);
 
     // Should run if props do change
     component(2);
-    assert_eq!(CALLS.load(Ordering::Relaxed), 2);
+    assert_eq!(CALLS.load(Ordering::Relaxed), MOUNT_RUNS + 1);
 
     component(3);
-    assert_eq!(CALLS.load(Ordering::Relaxed), 3);
+    assert_eq!(CALLS.load(Ordering::Relaxed), MOUNT_RUNS + 2);
 }
 
 #[test]
@@ -52,21 +55,122 @@ fn any_deps_should_work() {
         );
     }
 
-    mount_fiber(None, "root", |deps| component(deps)).unwrap();
+    mount_fiber(None, "root", component).unwrap();
 
     let component = |deps| call_fiber::<Vec<Box<dyn DynEq>>, ()>("root", deps).unwrap();
 
     component(vec![Box::new(MyStruct { x: 1 }), Box::new(1)]);
-    assert_eq!(CALLS.load(Ordering::Relaxed), 1);
+    assert_eq!(CALLS.load(Ordering::Relaxed), MOUNT_RUNS);
 
     // Shouldn't run if same deps
     component(vec![Box::new(MyStruct { x: 1 }), Box::new(1)]);
-    assert_eq!(CALLS.load(Ordering::Relaxed), 1);
+    assert_eq!(CALLS.load(Ordering::Relaxed), MOUNT_RUNS);
 
     // Shoul run if deps change
     component(vec![Box::new(MyStruct { x: 2 }), Box::new(1)]);
-    assert_eq!(CALLS.load(Ordering::Relaxed), 2);
+    assert_eq!(CALLS.load(Ordering::Relaxed), MOUNT_RUNS + 1);
 
     component(vec![Box::new(MyStruct { x: 2 }), Box::new(2)]);
-    assert_eq!(CALLS.load(Ordering::Relaxed), 3);
+    assert_eq!(CALLS.load(Ordering::Relaxed), MOUNT_RUNS + 2);
+}
+
+#[test]
+fn cleanup_runs_before_next_effect_and_on_unmount() {
+    use hooks_rs::unmount_fiber;
+    use std::cell::RefCell;
+
+    thread_local! {
+        static LOG: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+    }
+
+    fn component(dep: i32) {
+        use_effect(
+            &mut || {
+                LOG.with(|l| l.borrow_mut().push(format!("run {dep}")));
+                move || LOG.with(|l| l.borrow_mut().push(format!("cleanup {dep}")))
+            },
+            (dep,),
+        );
+    }
+
+    mount_fiber(None, "root", component).unwrap();
+
+    call_fiber::<i32, ()>("root", 1).unwrap();
+    call_fiber::<i32, ()>("root", 2).unwrap();
+    unmount_fiber("root");
+
+    let expected: &[&str] = if cfg!(feature = "strict") {
+        &[
+            "run 1",
+            "cleanup 1",
+            "run 1",
+            "cleanup 1",
+            "run 2",
+            "cleanup 2",
+        ]
+    } else {
+        &["run 1", "cleanup 1", "run 2", "cleanup 2"]
+    };
+    LOG.with(|l| assert_eq!(*l.borrow(), expected));
+}
+
+#[test]
+fn tuple_deps_should_work() {
+    static CALLS: AtomicU64 = AtomicU64::new(0);
+
+    fn component(props: (i32, String)) {
+        let (a, b) = props;
+        use_effect(
+            &mut || {
+                CALLS.fetch_add(1, Ordering::Relaxed);
+            },
+            (a, b),
+        );
+    }
+
+    mount_fiber(None, "root", component).unwrap();
+
+    let component = |a, b: &str| call_fiber::<(i32, String), ()>("root", (a, b.into())).unwrap();
+
+    component(1, "a");
+    component(1, "a");
+    assert_eq!(CALLS.load(Ordering::Relaxed), MOUNT_RUNS);
+
+    component(1, "b");
+    assert_eq!(CALLS.load(Ordering::Relaxed), MOUNT_RUNS + 1);
+
+    component(2, "b");
+    assert_eq!(CALLS.load(Ordering::Relaxed), MOUNT_RUNS + 2);
+}
+
+#[test]
+fn unit_deps_run_once_and_every_render_always_runs() {
+    use hooks_rs::EveryRender;
+
+    static ONCE: AtomicU64 = AtomicU64::new(0);
+    static ALWAYS: AtomicU64 = AtomicU64::new(0);
+
+    fn component(_: ()) {
+        use_effect(
+            &mut || {
+                ONCE.fetch_add(1, Ordering::Relaxed);
+            },
+            (),
+        );
+        use_effect(
+            &mut || {
+                ALWAYS.fetch_add(1, Ordering::Relaxed);
+            },
+            EveryRender,
+        );
+    }
+
+    mount_fiber(None, "root", component).unwrap();
+
+    for _ in 0..3 {
+        call_fiber::<(), ()>("root", ()).unwrap();
+    }
+
+    assert_eq!(ONCE.load(Ordering::Relaxed), MOUNT_RUNS);
+    assert_eq!(ALWAYS.load(Ordering::Relaxed), MOUNT_RUNS + 2);
 }
@@ -6,7 +6,7 @@ use hooks_rs::{call_fiber, mount_fiber, use_state};
 fn should_work_single() {
     fn counter(_: ()) -> i32 {
         let (count, set_count) = use_state(|| 0);
-        set_count(|prev| prev + 1);
+        set_count.update(|prev| prev + 1);
         count
     }
 
@@ -29,8 +29,8 @@ fn should_work_multiple() {
         let (count, set_count) = use_state(|| MyNumber(0));
         let (text, set_text) = use_state(|| String::from("hi"));
 
-        set_count(|prev| MyNumber(prev.0 + 1));
-        set_text(|prev_text| format!("{prev_text}!"));
+        set_count.update(|prev| MyNumber(prev.0 + 1));
+        set_text.update(|prev_text| format!("{prev_text}!"));
 
         (count, text)
     }
@@ -72,3 +72,99 @@ fn initial_should_be_called_once() {
 fn usage_outside_fiber_causes_panic() {
     let _ = use_state(|| 0);
 }
+
+#[test]
+fn updates_are_queued_until_next_render() {
+    use hooks_rs::SetStateAction;
+
+    static UPDATES: AtomicU64 = AtomicU64::new(0);
+
+    fn component(_: ()) -> (i32, SetStateAction<i32>) {
+        use_state(|| 1)
+    }
+
+    mount_fiber(None, "root", component).unwrap();
+
+    let component = || call_fiber::<(), (i32, SetStateAction<i32>)>("root", ()).unwrap();
+
+    let (count, set_count) = component();
+    assert_eq!(count, 1);
+
+    set_count.update(|prev| {
+        UPDATES.fetch_add(1, Ordering::Relaxed);
+        prev + 1
+    });
+    set_count.update(|prev| prev * 10);
+    assert_eq!(UPDATES.load(Ordering::Relaxed), 0);
+
+    // Processed in order at the start of the next render.
+    let (count, _) = component();
+    assert_eq!(count, 20);
+    assert_eq!(UPDATES.load(Ordering::Relaxed), 1);
+}
+
+#[test]
+fn immediate_updates_apply_right_away() {
+    use hooks_rs::{SetStateAction, use_state_immediate};
+
+    static UPDATES: AtomicU64 = AtomicU64::new(0);
+
+    fn component(_: ()) -> (i32, SetStateAction<i32>) {
+        use_state_immediate(|| 1)
+    }
+
+    mount_fiber(None, "root", component).unwrap();
+
+    let component = || call_fiber::<(), (i32, SetStateAction<i32>)>("root", ()).unwrap();
+
+    let (_, set_count) = component();
+    set_count.update(|prev| {
+        UPDATES.fetch_add(1, Ordering::Relaxed);
+        prev + 1
+    });
+    assert_eq!(UPDATES.load(Ordering::Relaxed), 1);
+
+    let (count, _) = component();
+    assert_eq!(count, 2);
+}
+
+#[test]
+fn equal_updates_leave_fiber_clean() {
+    use hooks_rs::{SetStateAction, is_fiber_dirty, use_state_eq};
+
+    fn component(_: ()) -> (i32, SetStateAction<i32>) {
+        use_state_eq(|| 1)
+    }
+
+    mount_fiber(None, "root", component).unwrap();
+
+    let component = || call_fiber::<(), (i32, SetStateAction<i32>)>("root", ()).unwrap();
+
+    let (_, set_count) = component();
+    set_count.update(|prev| *prev);
+    assert!(!is_fiber_dirty("root").unwrap());
+
+    set_count.update(|prev| prev + 1);
+    assert!(is_fiber_dirty("root").unwrap());
+
+    let (count, set_count) = component();
+    assert_eq!(count, 2);
+
+    set_count.set(2);
+    assert!(!is_fiber_dirty("root").unwrap());
+}
+
+#[test]
+#[cfg(feature = "nightly")]
+fn setter_is_callable_on_nightly() {
+    fn counter(_: ()) -> i32 {
+        let (count, set_count) = use_state(|| 0);
+        set_count(|prev| prev + 1);
+        count
+    }
+
+    mount_fiber(None, "root", counter).unwrap();
+
+    assert_eq!(call_fiber::<(), i32>("root", ()).unwrap(), 0);
+    assert_eq!(call_fiber::<(), i32>("root", ()).unwrap(), 1);
+}